@@ -0,0 +1,272 @@
+use crate::reports::{Anchor, LineRange, Reports};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Lines of context hashed on each side of the covered range.
+const CONTEXT_LINES: usize = 3;
+
+/// Outcome of locating an anchored range in the current file content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Relocation {
+    /// The covered lines are still at the recorded range.
+    Unchanged,
+    /// The code was found elsewhere (or was edited between intact context).
+    Moved { range: LineRange, anchor: Anchor },
+    /// Neither the code nor its surrounding context could be found.
+    Orphaned,
+}
+
+/// What happened to one report during `relocate_reports`.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub id: String,
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ChangeKind {
+    Moved { from: LineRange, to: LineRange },
+    Orphaned,
+    Recovered { range: LineRange },
+    Anchored,
+}
+
+/// Whitespace-insensitive form of a line, so reindenting does not break an anchor.
+fn normalize(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    git2::Oid::hash_object(git2::ObjectType::Blob, bytes)
+        .map(|oid| oid.to_string())
+        .unwrap_or_default()
+}
+
+fn line_hash(line: &str) -> String {
+    let mut h = hash_bytes(normalize(line).as_bytes());
+    h.truncate(8);
+    h
+}
+
+fn block_hash(lines: &[&str]) -> String {
+    let joined = lines
+        .iter()
+        .map(|l| normalize(l))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut h = hash_bytes(joined.as_bytes());
+    h.truncate(16);
+    h
+}
+
+/// Fingerprint lines `start..=end` (1-based) of `text`. None if the range is past EOF.
+pub fn compute(text: &str, start: u32, end: u32) -> Option<Anchor> {
+    let lines: Vec<&str> = text.lines().collect();
+    anchor_at(&lines, (start as usize).saturating_sub(1), end as usize)
+}
+
+/// Anchor for the 0-based half-open window `s..e`.
+fn anchor_at(lines: &[&str], s: usize, e: usize) -> Option<Anchor> {
+    if s >= e || e > lines.len() {
+        return None;
+    }
+    Some(Anchor {
+        hash: block_hash(&lines[s..e]),
+        before: lines[s.saturating_sub(CONTEXT_LINES)..s]
+            .iter()
+            .map(|l| line_hash(l))
+            .collect(),
+        after: lines[e..(e + CONTEXT_LINES).min(lines.len())]
+            .iter()
+            .map(|l| line_hash(l))
+            .collect(),
+    })
+}
+
+/// Number of context lines around window `s..e` that still match the anchor.
+fn context_score(hashes: &[String], anchor: &Anchor, s: usize, e: usize) -> usize {
+    let before = anchor
+        .before
+        .iter()
+        .rev()
+        .enumerate()
+        .take_while(|(i, h)| s > *i && hashes[s - 1 - i] == **h)
+        .count();
+    let after = anchor
+        .after
+        .iter()
+        .enumerate()
+        .take_while(|(i, h)| e + i < hashes.len() && hashes[e + i] == **h)
+        .count();
+    before + after
+}
+
+/// Find where the anchored code is now.
+///
+/// Exact matches of the covered lines win, ranked by how much context still matches
+/// and then by distance from the old position. If the code itself changed, a window
+/// bracketed by the full original context on both sides is accepted instead.
+pub fn relocate(text: &str, range: &LineRange, anchor: &Anchor) -> Relocation {
+    if range.start == 0 || range.end < range.start {
+        return Relocation::Orphaned;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let len = (range.end - range.start + 1) as usize;
+    let old = range.start as usize - 1;
+    if old + len <= lines.len() && block_hash(&lines[old..old + len]) == anchor.hash {
+        return Relocation::Unchanged;
+    }
+
+    let hashes: Vec<String> = lines.iter().map(|l| line_hash(l)).collect();
+    let distance = |s: usize| (s as i64 - old as i64).unsigned_abs();
+
+    let best = (0..lines.len().saturating_sub(len - 1))
+        .filter(|&s| block_hash(&lines[s..s + len]) == anchor.hash)
+        .max_by(|&a, &b| {
+            context_score(&hashes, anchor, a, a + len)
+                .cmp(&context_score(&hashes, anchor, b, b + len))
+                .then(distance(b).cmp(&distance(a)))
+        });
+    if let Some(s) = best {
+        return moved(&lines, s, s + len);
+    }
+
+    if anchor.before.is_empty() || anchor.after.is_empty() {
+        return Relocation::Orphaned;
+    }
+    let matches_at = |ctx: &[String], at: usize| {
+        at + ctx.len() <= hashes.len() && hashes[at..at + ctx.len()] == *ctx
+    };
+    let nb = anchor.before.len();
+    let best = (nb..=hashes.len())
+        .filter(|&s| matches_at(&anchor.before, s - nb))
+        .filter_map(|s| {
+            (s + 1..=hashes.len())
+                .find(|&e| matches_at(&anchor.after, e))
+                .map(|e| (s, e))
+        })
+        .min_by_key(|&(s, _)| distance(s));
+    match best {
+        Some((s, e)) => moved(&lines, s, e),
+        None => Relocation::Orphaned,
+    }
+}
+
+fn moved(lines: &[&str], s: usize, e: usize) -> Relocation {
+    match anchor_at(lines, s, e) {
+        Some(anchor) => Relocation::Moved {
+            range: LineRange {
+                start: s as u32 + 1,
+                end: e as u32,
+            },
+            anchor,
+        },
+        None => Relocation::Orphaned,
+    }
+}
+
+/// Relocate every open report in place. Reports without an anchor get one computed at
+/// their current range. Returns what changed; callers decide whether to save.
+pub fn relocate_reports(repo_root: &Path, reports: &mut Reports) -> Vec<Change> {
+    let mut files: HashMap<String, Option<String>> = HashMap::new();
+    let mut changes = Vec::new();
+    for e in reports.entries.iter_mut() {
+        if !e.is_active() {
+            continue;
+        }
+        let text = files
+            .entry(e.path.clone())
+            .or_insert_with(|| std::fs::read_to_string(repo_root.join(&e.path)).ok());
+        let Some(text) = text.as_deref() else {
+            if !e.orphaned {
+                e.orphaned = true;
+                changes.push(change(&e.id, &e.path, ChangeKind::Orphaned));
+            }
+            continue;
+        };
+        let Some(anchor) = e.anchor.clone() else {
+            if let Some(anchor) = compute(text, e.range.start, e.range.end) {
+                e.anchor = Some(anchor);
+                changes.push(change(&e.id, &e.path, ChangeKind::Anchored));
+            }
+            continue;
+        };
+        match relocate(text, &e.range, &anchor) {
+            Relocation::Unchanged => {
+                if e.orphaned {
+                    e.orphaned = false;
+                    let range = e.range.clone();
+                    changes.push(change(&e.id, &e.path, ChangeKind::Recovered { range }));
+                }
+            }
+            Relocation::Moved { range, anchor } => {
+                let from = std::mem::replace(&mut e.range, range.clone());
+                e.anchor = Some(anchor);
+                e.orphaned = false;
+                changes.push(change(
+                    &e.id,
+                    &e.path,
+                    ChangeKind::Moved { from, to: range },
+                ));
+            }
+            Relocation::Orphaned => {
+                if !e.orphaned {
+                    e.orphaned = true;
+                    changes.push(change(&e.id, &e.path, ChangeKind::Orphaned));
+                }
+            }
+        }
+    }
+    changes
+}
+
+fn change(id: &str, path: &str, kind: ChangeKind) -> Change {
+    Change {
+        id: id.to_string(),
+        path: path.to_string(),
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str =
+        "use std::io;\n\nfn a() {\n    step_one();\n    step_two();\n}\n\nfn b() {}\n";
+
+    #[test]
+    fn follows_inserted_lines() {
+        let range = LineRange { start: 4, end: 5 };
+        let anchor = compute(ORIGINAL, 4, 5).unwrap();
+        assert_eq!(relocate(ORIGINAL, &range, &anchor), Relocation::Unchanged);
+
+        let edited = format!(
+            "// header\n// more\n{}",
+            ORIGINAL.replace("    step", "\tstep")
+        );
+        match relocate(&edited, &range, &anchor) {
+            Relocation::Moved { range, .. } => assert_eq!((range.start, range.end), (6, 7)),
+            other => panic!("expected move, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn edited_code_between_context_and_orphaned() {
+        let range = LineRange { start: 4, end: 5 };
+        let anchor = compute(ORIGINAL, 4, 5).unwrap();
+        let edited = ORIGINAL.replace(
+            "    step_two();\n",
+            "    step_two_fixed();\n    step_three();\n",
+        );
+        match relocate(&edited, &range, &anchor) {
+            Relocation::Moved { range, .. } => assert_eq!((range.start, range.end), (4, 6)),
+            other => panic!("expected move, got {:?}", other),
+        }
+        assert_eq!(
+            relocate("fn other() {}\n", &range, &anchor),
+            Relocation::Orphaned
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::check::Evaluation;
+use crate::config::Severity;
+use serde_json::json;
+
+/// Escape the message part of a workflow command.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property of a workflow command.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Render GitHub Actions workflow commands. Violations are `::error`; other open
+/// reports (with --all) are `::warning` for high/medium severity and `::notice` otherwise.
+pub fn github(items: &[Evaluation]) -> String {
+    let mut out = String::new();
+    for item in items {
+        let e = item.entry;
+        let command = if item.is_violation() {
+            "error"
+        } else {
+            match item.severity {
+                Some(Severity::Blocking) | Some(Severity::High) | Some(Severity::Medium) => {
+                    "warning"
+                }
+                Some(Severity::Low) | None => "notice",
+            }
+        };
+        let mut message = format!("[{}] {}", e.tag, e.message);
+        if item.is_violation() {
+            message.push_str(&format!(" ({})", item.failure_reason()));
+        }
+        out.push_str(&format!(
+            "::{} file={},line={},endLine={},title={}::{}\n",
+            command,
+            escape_property(&e.path),
+            e.range.start,
+            e.range.end,
+            escape_property(&e.id),
+            escape_data(&message)
+        ));
+    }
+    out
+}
+
+/// GitLab Code Quality severity: violations are `blocker` (blocking) or `critical`
+/// (expired); others follow the tag severity.
+fn gitlab_severity(item: &Evaluation) -> &'static str {
+    match item.severity {
+        Some(Severity::Blocking) => "blocker",
+        _ if item.is_violation() => "critical",
+        Some(Severity::High) => "major",
+        Some(Severity::Medium) => "minor",
+        Some(Severity::Low) | None => "info",
+    }
+}
+
+/// Render a GitLab Code Quality report (JSON array of issues).
+pub fn gitlab(items: &[Evaluation]) -> Result<String, String> {
+    let issues: Vec<serde_json::Value> = items
+        .iter()
+        .map(|item| {
+            let e = item.entry;
+            let fingerprint = git2::Oid::hash_object(
+                git2::ObjectType::Blob,
+                format!("codereport:{}", e.id).as_bytes(),
+            )
+            .map(|oid| oid.to_string())
+            .unwrap_or_else(|_| e.id.clone());
+            json!({
+                "description": format!("{}: {}", e.id, e.message),
+                "check_name": format!("codereport/{}", e.tag),
+                "fingerprint": fingerprint,
+                "severity": gitlab_severity(item),
+                "location": {
+                    "path": e.path,
+                    "lines": { "begin": e.range.start, "end": e.range.end },
+                },
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&issues)
+        .map(|s| s + "\n")
+        .map_err(|e| format!("serialize code quality report: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workflow_command_escaping() {
+        assert_eq!(escape_property("src/a,b:c.rs"), "src/a%2Cb%3Ac.rs");
+        assert_eq!(escape_data("50% done\nnext"), "50%25 done%0Anext");
+    }
+}
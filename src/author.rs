@@ -1,9 +1,13 @@
 use std::path::Path;
 
+use crate::codeowners::CodeOwners;
+
 #[derive(Debug, Clone, Default)]
 pub struct ResolvedAuthor {
     pub git: Option<String>,
+    /// First of `owners`.
     pub codeowner: Option<String>,
+    pub owners: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -53,22 +57,25 @@ fn blob_oid_at_head(repo: &git2::Repository, path: &str) -> Option<String> {
     Some(entry.id().to_string())
 }
 
-/// (1) CODEOWNERS: best match for path. (2) Fallback: git blame for line range (with cache).
+/// (1) CODEOWNERS: owners of path. (2) Fallback: git blame for line range (with cache).
 pub fn resolve_author(repo_root: &Path, path: &str, start: u32, end: u32) -> ResolvedAuthor {
     let mut author = ResolvedAuthor::default();
 
     // Try CODEOWNERS first
-    if let Some(codeowner) = codeowner_for_path(repo_root, path) {
-        author.codeowner = Some(codeowner);
+    if let Some(codeowners) = CodeOwners::load(repo_root) {
+        author.owners = codeowners.owners_for(path);
+        author.codeowner = author.owners.first().cloned();
     }
+    author.git = blame_email(repo_root, path, start, end);
+    author
+}
 
-    let repo = match git2::Repository::open(repo_root) {
-        Ok(r) => r,
-        Err(_) => return author,
-    };
+/// Email of the last committer of line `start` (the blame cache is keyed by range).
+pub fn blame_email(repo_root: &Path, path: &str, start: u32, end: u32) -> Option<String> {
+    let repo = git2::Repository::open(repo_root).ok()?;
     let file_path = repo_root.join(path);
     if !file_path.exists() {
-        return author;
+        return None;
     }
 
     let path_for_blame = Path::new(path);
@@ -82,10 +89,7 @@ pub fn resolve_author(repo_root: &Path, path: &str, start: u32, end: u32) -> Res
             .iter()
             .find(|e| e.path == path && e.start == start && e.end == end && e.oid == *oid)
         {
-            if author.git.is_none() && !entry.email.is_empty() {
-                author.git = Some(entry.email.clone());
-            }
-            return author;
+            return (!entry.email.is_empty()).then(|| entry.email.clone());
         }
     }
 
@@ -101,12 +105,9 @@ pub fn resolve_author(repo_root: &Path, path: &str, start: u32, end: u32) -> Res
     } else {
         None
     };
-    if author.git.is_none() && email_opt.is_some() {
-        author.git = email_opt.clone();
-    }
 
     // Persist to cache (only when we have OID; skip for new/uncommitted files)
-    if let (Some(oid), Some(email)) = (oid_opt, email_opt) {
+    if let (Some(oid), Some(email)) = (oid_opt, email_opt.clone()) {
         let mut cache = load_blame_cache(repo_root);
         // Remove existing entry with same key if any
         cache
@@ -122,62 +123,41 @@ pub fn resolve_author(repo_root: &Path, path: &str, start: u32, end: u32) -> Res
         save_blame_cache(repo_root, &cache);
     }
 
-    author
+    email_opt
 }
 
-/// Find CODEOWNERS: .git/CODEOWNERS or repo root CODEOWNERS.
-/// Returns the owner string for the best (last) matching rule (e.g. "@backend" or "user@example.com").
-fn codeowner_for_path(repo_root: &Path, path: &str) -> Option<String> {
-    let path_forward = path.replace('\\', "/");
-    let content = read_codeowners(repo_root)?;
-    let mut last_match: Option<String> = None;
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-        let mut tokens = line.split_whitespace();
-        let pattern = tokens.next()?;
-        let owners: Vec<&str> = tokens.collect();
-        if owners.is_empty() {
+/// Who last changed each line of `start..=end` in the working copy: (email, line count),
+/// most lines first. Uncommitted lines are counted under "(uncommitted)".
+pub fn blame_authors(repo_root: &Path, path: &str, start: u32, end: u32) -> Vec<(String, usize)> {
+    let Ok(repo) = git2::Repository::open(repo_root) else {
+        return Vec::new();
+    };
+    let Ok(committed) = repo.blame_file(Path::new(path), None) else {
+        return Vec::new();
+    };
+    // Blame the working copy so local edits do not shift attribution.
+    let working = std::fs::read(repo_root.join(path))
+        .ok()
+        .and_then(|content| committed.blame_buffer(&content).ok());
+    let blame = working.as_ref().unwrap_or(&committed);
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for line in start.max(1)..=end {
+        let Some(hunk) = blame.get_line(line as usize) else {
             continue;
+        };
+        let who = if hunk.final_commit_id().is_zero() {
+            "(uncommitted)".to_string()
+        } else {
+            hunk.final_signature()
+                .email()
+                .unwrap_or("(unknown)")
+                .to_string()
+        };
+        match counts.iter_mut().find(|(email, _)| *email == who) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((who, 1)),
         }
-        if codeowners_pattern_matches(pattern, &path_forward) {
-            last_match = Some(owners[0].to_string());
-        }
-    }
-    last_match
-}
-
-fn read_codeowners(repo_root: &Path) -> Option<String> {
-    let in_dot_git = repo_root.join(".git").join("CODEOWNERS");
-    if in_dot_git.exists() {
-        return std::fs::read_to_string(&in_dot_git).ok();
-    }
-    let at_root = repo_root.join("CODEOWNERS");
-    if at_root.exists() {
-        return std::fs::read_to_string(&at_root).ok();
-    }
-    None
-}
-
-/// Simple CODEOWNERS-style match: pattern can be path prefix or suffix.
-/// - "/path" or "path" matches if path starts with it (after stripping leading /).
-/// - "*" and "**" not fully implemented; we do prefix/suffix and exact.
-fn codeowners_pattern_matches(pattern: &str, path: &str) -> bool {
-    let pattern = pattern.trim_start_matches('/');
-    let path = path.trim_start_matches('/');
-    if pattern.is_empty() {
-        return false;
-    }
-    if pattern == "*" || path == pattern {
-        return true;
-    }
-    if pattern.ends_with('/') {
-        return path.starts_with(pattern) || path.starts_with(pattern.trim_end_matches('/'));
     }
-    path.starts_with(pattern)
-        || path == pattern
-        || path.ends_with(pattern)
-        || path.contains(&format!("/{}", pattern))
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
 }
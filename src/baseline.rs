@@ -0,0 +1,367 @@
+use crate::check::Evaluation;
+use crate::reports::Reports;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+const BASELINE_VERSION: u32 = 1;
+const BASELINE_FILENAME: &str = "baseline.yaml";
+/// Renumberings left by the merge driver, one `old new tag` per line.
+const RENUMBERED_FILENAME: &str = ".renumbered";
+
+/// Snapshot of accepted violations for `check --ratchet`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub created_at: String,
+    pub entries: Vec<BaselineEntry>,
+    /// Violations per tag; may only go down.
+    pub counts: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BaselineEntry {
+    pub id: String,
+    pub tag: String,
+    /// Why it was a violation when snapshotted (e.g. "blocking severity").
+    pub reason: String,
+}
+
+/// Result of comparing current violations with the baseline.
+#[derive(Debug, Default)]
+pub struct Ratchet {
+    /// Violating report IDs that are not in the baseline.
+    pub new_ids: HashSet<String>,
+    /// Tags whose violation count went up: (tag, baseline, now).
+    pub increased: Vec<(String, u32, u32)>,
+    /// Baseline entries dropped because they no longer violate.
+    pub pruned: Vec<String>,
+}
+
+fn tag_counts<'a>(tags: impl Iterator<Item = &'a str>) -> BTreeMap<String, u32> {
+    let mut counts = BTreeMap::new();
+    for tag in tags {
+        *counts.entry(tag.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+impl Baseline {
+    pub fn from_violations(violations: &[&Evaluation]) -> Baseline {
+        let entries: Vec<BaselineEntry> = violations
+            .iter()
+            .map(|ev| BaselineEntry {
+                id: ev.entry.id.clone(),
+                tag: ev.entry.tag.clone(),
+                reason: ev.failure_reason(),
+            })
+            .collect();
+        Baseline {
+            version: BASELINE_VERSION,
+            created_at: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            counts: tag_counts(entries.iter().map(|e| e.tag.as_str())),
+            entries,
+        }
+    }
+
+    /// Compare current violations with the baseline and shrink the baseline to what
+    /// still violates: fixed entries are dropped and per-tag counts only go down.
+    pub fn ratchet(&mut self, violations: &[&Evaluation]) -> Ratchet {
+        let current_ids: HashSet<&str> = violations.iter().map(|ev| ev.entry.id.as_str()).collect();
+        let known: HashSet<&str> = self.entries.iter().map(|e| e.id.as_str()).collect();
+        let mut result = Ratchet {
+            new_ids: current_ids
+                .iter()
+                .filter(|id| !known.contains(*id))
+                .map(|id| id.to_string())
+                .collect(),
+            ..Ratchet::default()
+        };
+
+        let now = tag_counts(violations.iter().map(|ev| ev.entry.tag.as_str()));
+        for (tag, &n) in &now {
+            let before = self.counts.get(tag).copied().unwrap_or(0);
+            if n > before {
+                result.increased.push((tag.clone(), before, n));
+            }
+        }
+
+        self.entries.retain(|e| {
+            let keep = current_ids.contains(e.id.as_str());
+            if !keep {
+                result.pruned.push(e.id.clone());
+            }
+            keep
+        });
+        let kept = tag_counts(self.entries.iter().map(|e| e.tag.as_str()));
+        for (tag, count) in self.counts.iter_mut() {
+            *count = (*count).min(kept.get(tag).copied().unwrap_or(0));
+        }
+        self.counts.retain(|_, n| *n > 0);
+        result
+    }
+
+    /// Drop one report (e.g. after resolve/delete). Returns false if it was not listed.
+    pub fn forget(&mut self, id: &str) -> bool {
+        let Some(pos) = self.entries.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        let entry = self.entries.remove(pos);
+        if let Some(n) = self.counts.get_mut(&entry.tag) {
+            *n = n.saturating_sub(1);
+            if *n == 0 {
+                self.counts.remove(&entry.tag);
+            }
+        }
+        true
+    }
+
+    /// Follow a report renumbered from `from` to `to`. When several reports shared `from`
+    /// and were all listed, a later listing (preferably with the report's tag) moves to
+    /// `to`. A single listing cannot be attributed and stays with `from`; if the renumbered
+    /// report is the one that violates, the next `check --ratchet` reports it as new.
+    /// Returns false if nothing changed.
+    pub fn renumber(&mut self, from: &str, to: &str, tag: &str) -> bool {
+        if self.entries.iter().any(|e| e.id == to) {
+            return false;
+        }
+        let listed: Vec<usize> = (0..self.entries.len())
+            .filter(|&i| self.entries[i].id == from)
+            .collect();
+        match listed.as_slice() {
+            [] | [_] => false,
+            [_, rest @ ..] => {
+                let pick = *rest
+                    .iter()
+                    .rev()
+                    .find(|&&i| self.entries[i].tag == tag)
+                    .unwrap_or(&rest[rest.len() - 1]);
+                self.entries[pick].id = to.to_string();
+                true
+            }
+        }
+    }
+}
+
+fn baseline_path(repo_root: &Path) -> std::path::PathBuf {
+    repo_root.join(".codereports").join(BASELINE_FILENAME)
+}
+
+/// Load the baseline, or `None` if none was recorded. Renumberings left by the merge
+/// driver are not applied; see `apply_deferred`.
+pub fn load_baseline(repo_root: &Path) -> Result<Option<Baseline>, String> {
+    let path = baseline_path(repo_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("read baseline: {}", e))?;
+    let baseline: Baseline =
+        serde_yaml::from_str(&content).map_err(|e| format!("invalid baseline.yaml: {}", e))?;
+    if baseline.version != BASELINE_VERSION {
+        return Err(format!(
+            "unsupported baseline version: {} (expected {})",
+            baseline.version, BASELINE_VERSION
+        ));
+    }
+    Ok(Some(baseline))
+}
+
+fn renumbered_path(repo_root: &Path) -> std::path::PathBuf {
+    repo_root.join(".codereports").join(RENUMBERED_FILENAME)
+}
+
+/// Apply the renumberings left by `defer_renumbering` to `baseline` in memory. The file
+/// is removed by the next `save_baseline`, so callers that save must apply it first.
+/// Returns true if the baseline changed.
+pub fn apply_deferred(repo_root: &Path, baseline: &mut Baseline) -> bool {
+    let Ok(content) = std::fs::read_to_string(renumbered_path(repo_root)) else {
+        return false;
+    };
+    let mut changed = false;
+    for line in content.lines() {
+        if let [from, to, tag] = line.split_whitespace().collect::<Vec<_>>()[..] {
+            changed |= baseline.renumber(from, to, tag);
+        }
+    }
+    changed
+}
+
+/// Write the baseline and drop the deferred renumberings, which it now includes (or, for
+/// a fresh snapshot, no longer needs).
+pub fn save_baseline(repo_root: &Path, baseline: &Baseline) -> Result<(), String> {
+    let dest = baseline_path(repo_root);
+    let yaml = serde_yaml::to_string(baseline).map_err(|e| format!("serialize baseline: {}", e))?;
+    let mut temp = dest.clone();
+    temp.set_extension("yaml.tmp");
+    std::fs::write(&temp, yaml).map_err(|e| format!("write baseline: {}", e))?;
+    std::fs::rename(&temp, &dest).map_err(|e| format!("rename baseline: {}", e))?;
+    let renumbered = renumbered_path(repo_root);
+    if renumbered.exists() {
+        std::fs::remove_file(&renumbered)
+            .map_err(|e| format!("remove {}: {}", renumbered.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Remove a resolved or deleted report from the baseline, if there is one.
+pub fn forget_in_baseline(repo_root: &Path, id: &str) -> Result<(), String> {
+    forget_all_in_baseline(repo_root, &[id])
+}
+
+/// Like `forget_in_baseline` for several reports, saving the baseline once.
+pub fn forget_all_in_baseline(repo_root: &Path, ids: &[&str]) -> Result<(), String> {
+    if let Some(mut baseline) = load_baseline(repo_root)? {
+        let mut changed = apply_deferred(repo_root, &mut baseline);
+        for id in ids {
+            changed |= baseline.forget(id);
+        }
+        if changed {
+            save_baseline(repo_root, &baseline)?;
+        }
+    }
+    Ok(())
+}
+
+/// (old ID, new ID, tag) for renumbered reports, looked up in `reports` by new ID.
+fn with_tags<'a>(
+    reports: &'a Reports,
+    renumbered: &'a [(String, String)],
+) -> impl Iterator<Item = (&'a str, &'a str, &'a str)> {
+    renumbered.iter().filter_map(|(from, to)| {
+        let entry = reports.entries.iter().find(|e| e.id == *to)?;
+        Some((from.as_str(), to.as_str(), entry.tag.as_str()))
+    })
+}
+
+/// Carry (old, new) renumberings in `reports` over to the baseline, if there is one.
+pub fn renumber_in_baseline(
+    repo_root: &Path,
+    reports: &Reports,
+    renumbered: &[(String, String)],
+) -> Result<(), String> {
+    if let Some(mut baseline) = load_baseline(repo_root)? {
+        let mut changed = apply_deferred(repo_root, &mut baseline);
+        for (from, to, tag) in with_tags(reports, renumbered) {
+            changed |= baseline.renumber(from, to, tag);
+        }
+        if changed {
+            save_baseline(repo_root, &baseline)?;
+        }
+    }
+    Ok(())
+}
+
+/// Like `renumber_in_baseline`, but only recorded in `.renumbered`: `check --ratchet`
+/// applies it, and the next command that saves the baseline writes it in. The merge
+/// driver uses this: git may still overwrite baseline.yaml later in the same merge.
+pub fn defer_renumbering(
+    repo_root: &Path,
+    reports: &Reports,
+    renumbered: &[(String, String)],
+) -> Result<(), String> {
+    use std::io::Write;
+    let path = renumbered_path(repo_root);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("open {}: {}", path.display(), e))?;
+    for (from, to, tag) in with_tags(reports, renumbered) {
+        writeln!(file, "{} {} {}", from, to, tag)
+            .map_err(|e| format!("write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Severity;
+    use crate::reports::ReportEntry;
+
+    fn entry(id: &str, tag: &str) -> ReportEntry {
+        ReportEntry {
+            tag: tag.to_string(),
+            ..ReportEntry::sample(id, "src/lib.rs")
+        }
+    }
+
+    fn violation(e: &ReportEntry) -> Evaluation<'_> {
+        Evaluation {
+            entry: e,
+            severity: Some(Severity::Blocking),
+            is_expired: false,
+            days_until_expiry: None,
+            extension_violation: None,
+        }
+    }
+
+    #[test]
+    fn ratchet_flags_new_and_shrinks() {
+        let (a, b, c) = (
+            entry("CR-000001", "critical"),
+            entry("CR-000002", "critical"),
+            entry("CR-000003", "critical"),
+        );
+        let (va, vb, vc) = (violation(&a), violation(&b), violation(&c));
+        let mut baseline = Baseline::from_violations(&[&va, &vb]);
+        assert_eq!(baseline.counts.get("critical"), Some(&2));
+
+        // CR-000002 fixed, CR-000003 is new.
+        let r = baseline.ratchet(&[&va, &vc]);
+        assert!(r.new_ids.contains("CR-000003"));
+        assert!(r.increased.is_empty());
+        assert_eq!(r.pruned, vec!["CR-000002".to_string()]);
+        assert_eq!(baseline.entries.len(), 1);
+
+        // Only CR-000001 left; it is fixed too and the baseline empties.
+        let r = baseline.ratchet(&[]);
+        assert!(r.new_ids.is_empty());
+        assert!(baseline.entries.is_empty());
+        assert!(baseline.counts.is_empty());
+    }
+
+    #[test]
+    fn renumber_moves_a_listing_only_when_it_can_tell_which() {
+        let (a, b) = (entry("CR-000001", "critical"), entry("CR-000001", "buggy"));
+        let (va, vb) = (violation(&a), violation(&b));
+        let mut both = Baseline::from_violations(&[&va, &vb]);
+        assert!(both.renumber("CR-000001", "CR-000002", "buggy"));
+        let ids: Vec<(&str, &str)> = both
+            .entries
+            .iter()
+            .map(|e| (e.id.as_str(), e.tag.as_str()))
+            .collect();
+        assert_eq!(ids, vec![("CR-000001", "critical"), ("CR-000002", "buggy")]);
+        assert!(!both.renumber("CR-000001", "CR-000002", "buggy"));
+
+        // A single listing could be either report's: it stays where it is.
+        let mut one = Baseline::from_violations(&[&va]);
+        assert!(!one.renumber("CR-000001", "CR-000002", "buggy"));
+        assert!(!one.renumber("CR-000001", "CR-000003", "critical"));
+        assert_eq!(one.entries.len(), 1);
+        assert_eq!(one.entries[0].id, "CR-000001");
+
+        // Deferred renumberings are applied in memory; only a save writes them in.
+        let dir = std::env::temp_dir().join(format!("codereport-baseline-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".codereports")).unwrap();
+        save_baseline(&dir, &Baseline::from_violations(&[&va, &vb])).unwrap();
+        let reports = Reports {
+            version: 1,
+            entries: vec![a.clone(), entry("CR-000004", "buggy")],
+        };
+        let moved = [("CR-000001".to_string(), "CR-000004".to_string())];
+        defer_renumbering(&dir, &reports, &moved).unwrap();
+        let mut loaded = load_baseline(&dir).unwrap().unwrap();
+        assert!(!loaded.entries.iter().any(|e| e.id == "CR-000004"));
+        assert!(apply_deferred(&dir, &mut loaded));
+        assert!(loaded.entries.iter().any(|e| e.id == "CR-000004"));
+        assert!(renumbered_path(&dir).exists());
+
+        forget_in_baseline(&dir, "CR-000001").unwrap();
+        let saved = load_baseline(&dir).unwrap().unwrap();
+        let ids: Vec<&str> = saved.entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["CR-000004"]);
+        assert!(!renumbered_path(&dir).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::config::{self, Config};
+use crate::edit::Edit;
+use crate::reports::{ReportEntry, Reports, Status};
+
+/// What a bulk command does to each selected report.
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// Close (or otherwise transition) with an optional note.
+    Transition {
+        to: Status,
+        note: Option<String>,
+    },
+    Delete,
+    Edit(Edit),
+}
+
+/// One report the action will change, with the updated entry (unused for `Delete`).
+#[derive(Debug)]
+pub struct Planned {
+    pub index: usize,
+    pub updated: ReportEntry,
+    /// Changed fields for edits; empty otherwise.
+    pub changed: Vec<&'static str>,
+}
+
+/// The outcome of running an action on a selection without saving anything.
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub changes: Vec<Planned>,
+    /// (id, reason) for selected reports the action does not apply to.
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Run `action` on copies of the reports at `selected` (indices into `reports.entries`).
+/// Reports it is not valid for, or that it would leave unchanged, are skipped.
+pub fn plan(
+    cfg: &Config,
+    repo_root: &Path,
+    reports: &Reports,
+    selected: &[usize],
+    action: &Action,
+    actor: Option<String>,
+) -> Plan {
+    let mut plan = Plan::default();
+    for &index in selected {
+        let entry = &reports.entries[index];
+        let mut updated = entry.clone();
+        let outcome = match action {
+            Action::Transition { to, note } => config::validate_transition(cfg, &entry.status, *to)
+                .map(|()| {
+                    updated.transition(*to, actor.clone(), note.clone());
+                    Vec::new()
+                }),
+            Action::Delete => Ok(Vec::new()),
+            Action::Edit(change) => {
+                let source = std::fs::read_to_string(repo_root.join(&entry.path)).ok();
+                change
+                    .apply(cfg, &mut updated, source.as_deref(), actor.clone())
+                    .and_then(|changed| {
+                        if changed.is_empty() {
+                            Err("already up to date".to_string())
+                        } else {
+                            Ok(changed)
+                        }
+                    })
+            }
+        };
+        match outcome {
+            Ok(changed) => plan.changes.push(Planned {
+                index,
+                updated,
+                changed,
+            }),
+            Err(e) => plan.skipped.push((entry.id.clone(), e)),
+        }
+    }
+    plan
+}
+
+/// Write the planned changes into `reports` (in memory; the caller saves once).
+pub fn apply(reports: &mut Reports, plan: Plan, action: &Action) {
+    if let Action::Delete = action {
+        let doomed: HashSet<usize> = plan.changes.iter().map(|p| p.index).collect();
+        let mut index = 0;
+        reports.entries.retain(|_| {
+            let keep = !doomed.contains(&index);
+            index += 1;
+            keep
+        });
+        return;
+    }
+    for planned in plan.changes {
+        reports.entries[planned.index] = planned.updated;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, tag: &str, status: &str) -> ReportEntry {
+        ReportEntry {
+            tag: tag.to_string(),
+            status: status.to_string(),
+            ..ReportEntry::sample(id, "missing.rs")
+        }
+    }
+
+    #[test]
+    fn plans_then_applies_and_skips_invalid() {
+        let cfg = config::default_config();
+        let mut reports = Reports {
+            version: 1,
+            entries: vec![
+                entry("CR-000001", "todo", "open"),
+                entry("CR-000002", "todo", "resolved"),
+                entry("CR-000003", "refactor", "open"),
+            ],
+        };
+        let root = Path::new("/nonexistent");
+
+        let resolve = Action::Transition {
+            to: Status::Resolved,
+            note: Some("module removed".to_string()),
+        };
+        let p = plan(&cfg, root, &reports, &[0, 1], &resolve, None);
+        assert_eq!(p.changes.len(), 1);
+        assert_eq!(p.skipped[0].0, "CR-000002");
+        // Planning does not touch the reports.
+        assert_eq!(reports.entries[0].status, "open");
+        apply(&mut reports, p, &resolve);
+        assert_eq!(reports.entries[0].status, "resolved");
+        assert_eq!(reports.entries[0].history.len(), 1);
+
+        let retag = Action::Edit(Edit {
+            tag: Some("refactor".to_string()),
+            ..Edit::default()
+        });
+        let p = plan(&cfg, root, &reports, &[1, 2], &retag, None);
+        assert_eq!(p.changes.len(), 1);
+        assert_eq!(p.changes[0].changed, vec!["tag", "expires_at"]);
+        assert_eq!(
+            p.skipped,
+            vec![("CR-000003".to_string(), "already up to date".to_string())]
+        );
+
+        let p = plan(&cfg, root, &reports, &[0, 2], &Action::Delete, None);
+        apply(&mut reports, p, &Action::Delete);
+        let ids: Vec<&str> = reports.entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["CR-000002"]);
+    }
+}
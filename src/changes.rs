@@ -0,0 +1,121 @@
+use crate::reports::LineRange;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Lines changed relative to a base, per repo-relative path (new-side line numbers).
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    files: HashMap<String, Vec<(u32, u32)>>,
+}
+
+impl ChangedLines {
+    /// True if any changed hunk in `path` overlaps `range` (inclusive).
+    pub fn overlaps(&self, path: &str, range: &LineRange) -> bool {
+        self.files
+            .get(path)
+            .map(|hunks| {
+                hunks
+                    .iter()
+                    .any(|&(start, end)| start <= range.end && end >= range.start)
+            })
+            .unwrap_or(false)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn add(&mut self, path: String, start: u32, end: u32) {
+        self.files.entry(path).or_default().push((start, end));
+    }
+}
+
+/// New-side lines of a hunk. A pure deletion (no new lines) is placed on the line after
+/// it; git reports it at the line before (`new_start`), or 0 at the top of the file.
+fn hunk_lines(new_start: u32, new_lines: u32) -> (u32, u32) {
+    if new_lines == 0 {
+        (new_start + 1, new_start + 1)
+    } else {
+        (new_start, new_start + new_lines - 1)
+    }
+}
+
+/// Changed lines between the merge base of `base` and HEAD, and HEAD (or the working
+/// tree, including staged changes, when `worktree` is set). Renames are followed so
+/// hunks are keyed by the new path. Pure deletions count as touching the line after them.
+pub fn changed_lines(repo_root: &Path, base: &str, worktree: bool) -> Result<ChangedLines, String> {
+    let repo = git2::Repository::open(repo_root).map_err(|e| format!("open repository: {}", e))?;
+    let base_commit = repo
+        .revparse_single(base)
+        .and_then(|o| o.peel_to_commit())
+        .map_err(|e| format!("resolve base '{}': {}", base, e))?;
+    let head_commit = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| format!("resolve HEAD: {}", e))?;
+    let merge_base = repo
+        .merge_base(base_commit.id(), head_commit.id())
+        .and_then(|oid| repo.find_commit(oid))
+        .unwrap_or(base_commit);
+    let base_tree = merge_base
+        .tree()
+        .map_err(|e| format!("read base tree: {}", e))?;
+
+    let mut opts = git2::DiffOptions::new();
+    opts.context_lines(0);
+    let mut diff = if worktree {
+        repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))
+    } else {
+        let head_tree = head_commit
+            .tree()
+            .map_err(|e| format!("read HEAD tree: {}", e))?;
+        repo.diff_tree_to_tree(Some(&base_tree), Some(&head_tree), Some(&mut opts))
+    }
+    .map_err(|e| format!("diff against '{}': {}", base, e))?;
+    let mut find = git2::DiffFindOptions::new();
+    find.renames(true);
+    diff.find_similar(Some(&mut find))
+        .map_err(|e| format!("detect renames: {}", e))?;
+
+    let mut changed = ChangedLines::default();
+    diff.foreach(
+        &mut |_, _| true,
+        None,
+        Some(&mut |delta, hunk| {
+            if let Some(path) = delta.new_file().path() {
+                let path = path.to_string_lossy().replace('\\', "/");
+                let (start, end) = hunk_lines(hunk.new_start(), hunk.new_lines());
+                changed.add(path, start, end);
+            }
+            true
+        }),
+        None,
+    )
+    .map_err(|e| format!("read diff: {}", e))?;
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlap_is_inclusive() {
+        let mut c = ChangedLines::default();
+        c.add("src/a.rs".to_string(), 10, 12);
+        let r = |start, end| LineRange { start, end };
+        assert!(c.overlaps("src/a.rs", &r(12, 20)));
+        assert!(c.overlaps("src/a.rs", &r(1, 10)));
+        assert!(!c.overlaps("src/a.rs", &r(13, 20)));
+        assert!(!c.overlaps("src/b.rs", &r(10, 12)));
+    }
+
+    #[test]
+    fn deletions_touch_the_following_line() {
+        // Lines 5-6 removed: git reports new_start 4 (the line before), new_lines 0.
+        assert_eq!(hunk_lines(4, 0), (5, 5));
+        // Removed from the top of the file.
+        assert_eq!(hunk_lines(0, 0), (1, 1));
+        assert_eq!(hunk_lines(4, 3), (4, 6));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::config::{self, Config, Severity};
+use crate::reports::{self, ReportEntry, Reports};
+
+/// A report together with the policy fields computed from config and today's date.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Evaluation<'a> {
+    #[serde(flatten)]
+    pub entry: &'a ReportEntry,
+    /// Severity of the report's tag; `None` if the tag is not in config.
+    pub severity: Option<Severity>,
+    pub is_expired: bool,
+    /// Days until `expires_at` (negative when overdue); `None` without a valid date.
+    pub days_until_expiry: Option<i64>,
+    /// Set when the report was extended beyond its tag's `max_extensions` / `max_total_days`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_violation: Option<String>,
+}
+
+impl Evaluation<'_> {
+    /// Still active (open, in progress or reopened).
+    pub fn is_open(&self) -> bool {
+        self.entry.is_active()
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.severity == Some(Severity::Blocking)
+    }
+
+    /// Open and blocking, expired or over the extension limits: what fails `codereport check`.
+    pub fn is_violation(&self) -> bool {
+        self.is_open()
+            && self.severity.is_some()
+            && (self.is_blocking() || self.is_expired || self.extension_violation.is_some())
+    }
+
+    /// Why the report fails the check (e.g. "expired on 2026-01-01 (3 days ago)").
+    pub fn failure_reason(&self) -> String {
+        let mut reasons = Vec::new();
+        if self.is_blocking() {
+            reasons.push("blocking severity".to_string());
+        }
+        if self.is_expired {
+            reasons.push(format!(
+                "expired on {} ({} days ago)",
+                self.entry.expires_at.as_deref().unwrap_or("?"),
+                -self.days_until_expiry.unwrap_or(0)
+            ));
+        }
+        if let Some(ref why) = self.extension_violation {
+            reasons.push(why.clone());
+        }
+        reasons.join(", ")
+    }
+}
+
+pub fn today() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+pub fn evaluate<'a>(
+    cfg: &Config,
+    entry: &'a ReportEntry,
+    today: chrono::NaiveDate,
+) -> Evaluation<'a> {
+    let days_until_expiry = entry
+        .expires_at
+        .as_deref()
+        .and_then(reports::parse_date)
+        .map(|d| (d - today).num_days());
+    Evaluation {
+        entry,
+        severity: config::severity(cfg, &entry.tag).ok(),
+        is_expired: days_until_expiry.map(|d| d < 0).unwrap_or(false),
+        days_until_expiry,
+        extension_violation: {
+            let (count, total_days) = entry.extensions();
+            config::check_extension_limits(cfg, &entry.tag, count, total_days).err()
+        },
+    }
+}
+
+pub fn evaluate_all<'a>(
+    cfg: &Config,
+    reports: &'a Reports,
+    today: chrono::NaiveDate,
+) -> Vec<Evaluation<'a>> {
+    reports
+        .entries
+        .iter()
+        .map(|e| evaluate(cfg, e, today))
+        .collect()
+}
+
+/// Test fixture for the output formats: a blocking report whose message and path need
+/// escaping, an expired one and one that passes.
+#[cfg(test)]
+pub fn sample_reports() -> Vec<ReportEntry> {
+    vec![
+        ReportEntry {
+            tag: "critical".to_string(),
+            message: "lock <held> & \"released\"".to_string(),
+            ..ReportEntry::sample("CR-000001", "src/a&b.rs")
+        },
+        ReportEntry {
+            tag: "buggy".to_string(),
+            range: reports::LineRange { start: 7, end: 9 },
+            expires_at: Some("2026-01-01".to_string()),
+            ..ReportEntry::sample("CR-000002", "src/net.rs")
+        },
+        ReportEntry::sample("CR-000003", "src/net.rs"),
+    ]
+}
+
+/// `sample_reports` evaluated against the default config on 2026-01-04.
+#[cfg(test)]
+pub fn evaluate_samples(entries: &[ReportEntry]) -> Vec<Evaluation<'_>> {
+    let cfg = config::default_config();
+    let today = reports::parse_date("2026-01-04").unwrap();
+    entries.iter().map(|e| evaluate(&cfg, e, today)).collect()
+}
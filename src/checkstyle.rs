@@ -0,0 +1,68 @@
+use crate::check::Evaluation;
+use crate::config::Severity;
+use crate::output::xml_escape;
+use std::collections::BTreeMap;
+
+fn severity(item: &Evaluation) -> &'static str {
+    if item.is_violation() {
+        return "error";
+    }
+    match item.severity {
+        Some(Severity::Blocking) | Some(Severity::High) | Some(Severity::Medium) => "warning",
+        Some(Severity::Low) | None => "info",
+    }
+}
+
+/// Render Checkstyle XML: one `<error>` per open report, grouped by file.
+/// Violations are `error`, high/medium severity `warning`, the rest `info`.
+pub fn render(items: &[Evaluation]) -> String {
+    let mut by_file: BTreeMap<&str, Vec<&Evaluation>> = BTreeMap::new();
+    for item in items {
+        by_file
+            .entry(item.entry.path.as_str())
+            .or_default()
+            .push(item);
+    }
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<checkstyle version=\"4.3\">\n");
+    for (path, items) in by_file {
+        out.push_str(&format!("  <file name=\"{}\">\n", xml_escape(path)));
+        for item in items {
+            let e = item.entry;
+            out.push_str(&format!(
+                "    <error line=\"{}\" severity=\"{}\" message=\"{}\" source=\"codereport.{}\"/>\n",
+                e.range.start,
+                severity(item),
+                xml_escape(&format!("{}: {}", e.id, e.message)),
+                xml_escape(&e.tag)
+            ));
+        }
+        out.push_str("  </file>\n");
+    }
+    out.push_str("</checkstyle>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+
+    #[test]
+    fn groups_by_file_and_escapes() {
+        let entries = check::sample_reports();
+        let xml = render(&check::evaluate_samples(&entries));
+        let expected = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<checkstyle version=\"4.3\">
+  <file name=\"src/a&amp;b.rs\">
+    <error line=\"1\" severity=\"error\" message=\"CR-000001: lock &lt;held&gt; &amp; &quot;released&quot;\" source=\"codereport.critical\"/>
+  </file>
+  <file name=\"src/net.rs\">
+    <error line=\"7\" severity=\"error\" message=\"CR-000002: m\" source=\"codereport.buggy\"/>
+    <error line=\"1\" severity=\"info\" message=\"CR-000003: m\" source=\"codereport.todo\"/>
+  </file>
+</checkstyle>
+";
+        assert_eq!(xml, expected);
+    }
+}
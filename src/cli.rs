@@ -1,12 +1,28 @@
+use crate::anchor;
 use crate::author;
+use crate::baseline;
+use crate::bulk;
+use crate::changes;
+use crate::check;
+use crate::codeowners;
 use crate::config;
+use crate::doctor;
+use crate::edit;
 use crate::html;
+use crate::merge;
+use crate::migrate;
+use crate::output;
+use crate::owners;
+use crate::query;
 use crate::repo;
 use crate::reports;
-use clap::{Parser, Subcommand};
+use crate::scan;
+use crate::search;
+use crate::show;
+use crate::sync;
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 use std::process::ExitCode;
-use std::str::FromStr;
 
 #[derive(Parser, Debug)]
 #[command(name = "codereport", version, about)]
@@ -27,29 +43,373 @@ pub enum Command {
         tag: String,
         #[arg(long)]
         message: String,
+        /// Assignee (email or @team; "me" for your git user.email)
+        #[arg(long, value_name = "WHO")]
+        assign: Option<String>,
     },
     /// List reports with optional filters
-    List {
+    List(ListArgs),
+    /// Delete a report by ID
+    Delete { id: String },
+    /// Change a report's message, tag, range, expiry or assignee (opens $EDITOR without options)
+    Edit(EditArgs),
+    /// Close a report as resolved (or wontfix / duplicate)
+    Resolve {
+        id: String,
+        /// Why it was closed; recorded in the report's history
         #[arg(long)]
-        tag: Option<String>,
+        reason: Option<String>,
+        /// Closing status
+        #[arg(long = "as", value_enum, default_value_t = CloseAs::Resolved)]
+        close_as: CloseAs,
+    },
+    /// Reopen a closed report
+    Reopen {
+        id: String,
         #[arg(long)]
-        status: Option<String>,
+        note: Option<String>,
+    },
+    /// Mark a report as in progress
+    Start {
+        id: String,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Assign a report to someone (email or @team; "me" for yourself, "none" to clear)
+    Assign { id: String, who: String },
+    /// Push back a report's expiry, with a reason recorded in its history
+    Extend {
+        id: String,
+        /// Days to add (to the current expiry, or to today if already expired)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=3650))]
+        days: u32,
+        /// Why the extension is needed
+        #[arg(long)]
+        reason: String,
+    },
+    /// Resolve, delete, edit or assign every report matching a filter (dry run without --yes)
+    #[command(subcommand)]
+    Bulk(BulkCommand),
+    /// Recompute report ownership after CODEOWNERS changes
+    #[command(subcommand)]
+    Owners(OwnersCommand),
+    /// Add a comment to a report's discussion thread
+    Comment {
+        id: String,
+        /// Comment text
+        text: String,
+    },
+    /// Show one report in full: policy status, source lines, blame, history and comments
+    Show {
+        id: String,
+        /// Disable colours (also off when stdout is not a terminal or NO_COLOR is set)
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Search report messages, paths, tags and comments (ranked, typo-tolerant)
+    Search {
+        #[arg(required = true)]
+        terms: Vec<String>,
+        /// Show at most N results
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+        /// Disable match highlighting (also off when stdout is not a terminal or NO_COLOR is set)
+        #[arg(long)]
+        no_color: bool,
     },
-    /// Delete a report by ID
-    Delete { id: String },
-    /// Mark a report as resolved
-    Resolve { id: String },
     /// CI check: fail if blocking or expired open reports
-    Check,
+    Check(CheckArgs),
+    /// Snapshot current violations into .codereports/baseline.yaml for check --ratchet
+    Baseline,
+    /// Re-find moved code for open reports and update their line ranges
+    Relocate {
+        /// Print what would change without writing reports.yaml
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Follow file renames and deletions (from git history) for all reports
+    Sync {
+        /// Print what would change without writing reports.yaml
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import TODO/FIXME-style comment markers as reports
+    Scan {
+        /// Print what would be added without writing reports.yaml
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Upgrade reports.yaml and config.yaml to the current format (keeps a .bak copy of each)
+    Migrate {
+        /// List the pending upgrade steps without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Validate every report against the config and the working tree
+    Doctor {
+        /// Apply the safe repairs (canonical spellings, dates, duplicate IDs, orphaning)
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Give reports that share an ID new IDs (keeps the first of each)
+    FixIds {
+        /// Print what would change without writing reports.yaml
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Git merge driver for reports.yaml (registered by `init`): merges reports by ID
+    MergeDriver {
+        /// Common ancestor version (%O)
+        base: PathBuf,
+        /// Our version (%A); the result is written here
+        ours: PathBuf,
+        /// Their version (%B)
+        theirs: PathBuf,
+    },
     /// Generate HTML dashboard
     Html {
         #[arg(long)]
         no_open: bool,
+        /// Only include reports matching this query (see `list --query`)
+        #[arg(long)]
+        query: Option<String>,
+    },
+}
+
+/// Report filters shared by commands that select several reports.
+#[derive(Args, Debug, Default)]
+pub struct FilterArgs {
+    /// Only reports whose path matches GLOB (e.g. 'legacy/**'); a directory matches everything under it
+    #[arg(long, value_name = "GLOB")]
+    pub path: Option<String>,
+    #[arg(long)]
+    pub tag: Option<String>,
+    #[arg(long)]
+    pub status: Option<String>,
+    /// Only reports assigned to WHO ("me" for your git user.email, "none" for unassigned)
+    #[arg(long, value_name = "WHO")]
+    pub assignee: Option<String>,
+    /// Filter expression, e.g. 'tag:critical AND path:src/net/** AND expires<30d'
+    #[arg(long, short = 'q')]
+    pub query: Option<String>,
+}
+
+impl FilterArgs {
+    /// Combine the options into one query (`None` if no filter was given).
+    fn to_query(&self) -> Result<Option<query::Query>, String> {
+        let quote = |v: &str| format!("\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut parts = Vec::new();
+        if let Some(ref path) = self.path {
+            parts.push(format!("path:{}", quote(path)));
+        }
+        if let Some(ref tag) = self.tag {
+            parts.push(format!("tag:{}", quote(tag)));
+        }
+        if let Some(ref status) = self.status {
+            parts.push(format!("status:{}", quote(status)));
+        }
+        if let Some(ref who) = self.assignee {
+            parts.push(format!("assignee:{}", quote(who.trim())));
+        }
+        if let Some(ref q) = self.query {
+            parts.push(format!("({})", q));
+        }
+        if parts.is_empty() {
+            return Ok(None);
+        }
+        query::Query::parse(&parts.join(" AND ")).map(Some)
+    }
+}
+
+/// Selection shared by the `bulk` subcommands.
+#[derive(Args, Debug)]
+pub struct BulkSelect {
+    #[command(flatten)]
+    pub filter: FilterArgs,
+    /// Apply the change (without it, only list the reports that would change)
+    #[arg(long)]
+    pub yes: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BulkCommand {
+    /// Close every matching report as resolved (or wontfix / duplicate)
+    Resolve {
+        #[command(flatten)]
+        select: BulkSelect,
+        /// Why they were closed; recorded in each report's history
+        #[arg(long)]
+        reason: Option<String>,
+        /// Closing status
+        #[arg(long = "as", value_enum, default_value_t = CloseAs::Resolved)]
+        close_as: CloseAs,
     },
+    /// Delete every matching report
+    Delete {
+        #[command(flatten)]
+        select: BulkSelect,
+    },
+    /// Change the message, tag, expiry or assignee of every matching report
+    Edit {
+        #[command(flatten)]
+        select: BulkSelect,
+        #[arg(long, value_name = "TEXT")]
+        set_message: Option<String>,
+        #[arg(long, value_name = "TAG")]
+        set_tag: Option<String>,
+        /// New expiry date (YYYY-MM-DD), or "never"
+        #[arg(long, value_name = "DATE")]
+        set_expires: Option<String>,
+        /// Email or @team, or "none" to clear
+        #[arg(long, value_name = "WHO")]
+        set_assignee: Option<String>,
+        /// Keep expires_at when the tag changes instead of recomputing it
+        #[arg(long)]
+        keep_expiry: bool,
+    },
+    /// Assign every matching report (email or @team; "me" for yourself, "none" to clear)
+    Assign {
+        who: String,
+        #[command(flatten)]
+        select: BulkSelect,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OwnersCommand {
+    /// Re-resolve the code owners (and with --blame, the author) of open reports
+    Refresh {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Also re-run git blame on each report's lines
+        #[arg(long)]
+        blame: bool,
+        /// Print what would change without writing reports.yaml
+        #[arg(long)]
+        dry_run: bool,
+        /// Without a CODEOWNERS file, clear the owners instead of refusing
+        #[arg(long)]
+        clear: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub filter: FilterArgs,
+    /// Comma-separated sort keys, '-' for descending (e.g. expires_at,-severity)
+    #[arg(long, allow_hyphen_values = true)]
+    pub sort: Option<String>,
+    /// Show at most N reports (after sorting)
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: output::Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CloseAs {
+    Resolved,
+    Wontfix,
+    Duplicate,
+}
+
+impl CloseAs {
+    fn status(self) -> reports::Status {
+        match self {
+            CloseAs::Resolved => reports::Status::Resolved,
+            CloseAs::Wontfix => reports::Status::Wontfix,
+            CloseAs::Duplicate => reports::Status::Duplicate,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct EditArgs {
+    pub id: String,
+    #[arg(long)]
+    pub message: Option<String>,
+    #[arg(long)]
+    pub tag: Option<String>,
+    /// New line range as start-end
+    #[arg(long, value_name = "START-END")]
+    pub range: Option<String>,
+    /// New expiry date (YYYY-MM-DD), or "never"
+    #[arg(long, value_name = "DATE")]
+    pub expires: Option<String>,
+    /// Email or @team, or "none" to clear
+    #[arg(long)]
+    pub assignee: Option<String>,
+    /// Keep expires_at when the tag changes instead of recomputing it
+    #[arg(long)]
+    pub keep_expiry: bool,
+}
+
+impl EditArgs {
+    fn to_edit(&self) -> Result<edit::Edit, String> {
+        let range = match self.range {
+            Some(ref r) => {
+                let (start, end) = parse_range(r)?;
+                Some(reports::LineRange { start, end })
+            }
+            None => None,
+        };
+        let clearable = |v: &Option<String>, clear: &str| {
+            v.as_ref().map(|s| {
+                let s = s.trim();
+                (!s.is_empty() && !s.eq_ignore_ascii_case(clear)).then(|| s.to_string())
+            })
+        };
+        Ok(edit::Edit {
+            message: self.message.clone(),
+            tag: self.tag.clone(),
+            range,
+            expires_at: clearable(&self.expires, "never"),
+            assignee: clearable(&self.assignee, "none"),
+            keep_expiry: self.keep_expiry,
+        })
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Output format for violations (table goes to stderr, others to stdout)
+    #[arg(long, value_enum, default_value_t)]
+    pub format: output::CheckFormat,
+    /// Write the output to a file instead of stdout/stderr
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+    /// Include every open report in the output, not only violations
+    #[arg(long)]
+    pub all: bool,
+    /// Only consider reports overlapping lines changed since the merge base with this ref
+    #[arg(long, value_name = "REF")]
+    pub base: Option<String>,
+    /// With --base: diff against the working tree (including staged changes) instead of HEAD
+    #[arg(long, requires = "base")]
+    pub worktree: bool,
+    /// With --base and --ratchet: also fail when changed lines fall inside an open blocking
+    /// report that the baseline accepts
+    #[arg(long, requires_all = ["base", "ratchet"])]
+    pub guard: bool,
+    /// Fail only on violations not in .codereports/baseline.yaml (or if a tag's count grows)
+    #[arg(long)]
+    pub ratchet: bool,
+    /// Only consider reports matching this query (see `list --query`)
+    #[arg(long)]
+    pub query: Option<String>,
+    /// Warn about open reports whose owners no longer appear in CODEOWNERS
+    #[arg(long)]
+    pub stale_owners: bool,
 }
 
 pub fn run() -> ExitCode {
     let cli = Cli::parse();
+    // Git runs the merge driver on temporary files; it needs no repository.
+    if let Command::MergeDriver { base, ours, theirs } = cli.command {
+        return cmd_merge_driver(&base, &ours, &theirs);
+    }
     let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     let repo_root = match repo::find_repo_root(&cwd) {
         Some(r) => r,
@@ -65,16 +425,52 @@ pub fn run() -> ExitCode {
             location,
             tag,
             message,
-        } => cmd_add(&repo_root, &location, &tag, &message),
-        Command::List { tag, status } => cmd_list(&repo_root, tag.as_deref(), status.as_deref()),
+            assign,
+        } => cmd_add(&repo_root, &location, &tag, &message, assign.as_deref()),
+        Command::List(args) => cmd_list(&repo_root, &args),
         Command::Delete { id } => cmd_delete(&repo_root, &id),
-        Command::Resolve { id } => cmd_resolve(&repo_root, &id),
-        Command::Check => cmd_check(&repo_root),
-        Command::Html { no_open } => cmd_html(&repo_root, no_open),
+        Command::Edit(args) => cmd_edit(&repo_root, &args),
+        Command::Resolve {
+            id,
+            reason,
+            close_as,
+        } => cmd_transition(&repo_root, &id, close_as.status(), reason),
+        Command::Reopen { id, note } => {
+            cmd_transition(&repo_root, &id, reports::Status::Reopened, note)
+        }
+        Command::Start { id, note } => {
+            cmd_transition(&repo_root, &id, reports::Status::InProgress, note)
+        }
+        Command::Assign { id, who } => cmd_assign(&repo_root, &id, &who),
+        Command::Extend { id, days, reason } => cmd_extend(&repo_root, &id, days, &reason),
+        Command::Bulk(bulk_command) => cmd_bulk(&repo_root, bulk_command),
+        Command::Owners(OwnersCommand::Refresh {
+            filter,
+            blame,
+            dry_run,
+            clear,
+        }) => cmd_owners_refresh(&repo_root, &filter, blame, dry_run, clear),
+        Command::Comment { id, text } => cmd_comment(&repo_root, &id, &text),
+        Command::Show { id, no_color } => cmd_show(&repo_root, &id, no_color),
+        Command::Check(args) => cmd_check(&repo_root, &args),
+        Command::Baseline => cmd_baseline(&repo_root),
+        Command::Relocate { dry_run } => cmd_relocate(&repo_root, dry_run),
+        Command::Sync { dry_run } => cmd_sync(&repo_root, dry_run),
+        Command::Scan { dry_run } => cmd_scan(&repo_root, dry_run),
+        Command::Search {
+            terms,
+            limit,
+            no_color,
+        } => cmd_search(&repo_root, &terms.join(" "), limit, no_color),
+        Command::Migrate { dry_run } => cmd_migrate(&repo_root, dry_run),
+        Command::Doctor { fix } => cmd_doctor(&repo_root, fix),
+        Command::FixIds { dry_run } => cmd_fix_ids(&repo_root, dry_run),
+        Command::MergeDriver { .. } => unreachable!("handled above"),
+        Command::Html { no_open, query } => cmd_html(&repo_root, no_open, query.as_deref()),
     }
 }
 
-const GITIGNORE_BLOCK: &str = "\n# codereport (generated dashboard and local blame cache)\n.codereports/html/\n.codereports/.blame-cache\n";
+const GITIGNORE_BLOCK: &str = "\n# codereport (generated dashboard and local caches)\n.codereports/html/\n.codereports/.blame-cache\n.codereports/.search-index\n.codereports/.renumbered\n.codereports/*.bak\n";
 
 fn ensure_root_gitignore(repo_root: &std::path::Path) -> Result<(), String> {
     let root_gitignore = repo_root.join(".gitignore");
@@ -84,12 +480,22 @@ fn ensure_root_gitignore(repo_root: &std::path::Path) -> Result<(), String> {
         String::new()
     };
     let already_has = content.contains(".codereports/html/") || content.contains("# codereport");
-    if already_has {
-        return Ok(());
-    }
-    let block = GITIGNORE_BLOCK.trim_start_matches('\n');
+    let block = if already_has {
+        // Add entries introduced after the block was written.
+        let missing: Vec<&str> = GITIGNORE_BLOCK
+            .lines()
+            .filter(|l| l.starts_with(".codereports/"))
+            .filter(|l| !content.lines().any(|c| c.trim() == *l))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        format!("{}\n", missing.join("\n"))
+    } else {
+        GITIGNORE_BLOCK.trim_start_matches('\n').to_string()
+    };
     let new_content = if content.trim().is_empty() {
-        block.to_string()
+        block
     } else {
         format!("{}\n{}", content.trim_end_matches('\n'), block)
     };
@@ -107,6 +513,13 @@ fn cmd_init(repo_root: &std::path::Path) -> ExitCode {
         eprintln!("error: failed to update repo root .gitignore: {}", e);
         return ExitCode::from(1);
     }
+    if let Err(e) = merge::install_driver(repo_root) {
+        eprintln!(
+            "error: failed to register the reports.yaml merge driver: {}",
+            e
+        );
+        return ExitCode::from(1);
+    }
     let config_path = dir.join("config.yaml");
     if !config_path.exists() {
         if let Err(e) = config::write_default_config(repo_root) {
@@ -130,6 +543,12 @@ fn parse_location(location: &str) -> Result<(String, u32, u32), String> {
         return Err("path is empty".to_string());
     }
     let path = path.replace('\\', "/");
+    let (start, end) = parse_range(range_part)?;
+    Ok((path, start, end))
+}
+
+/// Parse "start-end" into (start, end).
+fn parse_range(range_part: &str) -> Result<(u32, u32), String> {
     let dash = range_part
         .find('-')
         .ok_or_else(|| "expected start-end range".to_string())?;
@@ -146,10 +565,30 @@ fn parse_location(location: &str) -> Result<(String, u32, u32), String> {
     if start == 0 || end < start {
         return Err("invalid range (start >= 1, end >= start)".to_string());
     }
-    Ok((path, start, end))
+    Ok((start, end))
+}
+
+/// Resolve an assignee argument: "me" is the git `user.email`, "none" (or empty) is nobody.
+fn resolve_assignee(repo_root: &std::path::Path, who: &str) -> Result<Option<String>, String> {
+    let who = who.trim();
+    if who.is_empty() || who.eq_ignore_ascii_case("none") {
+        Ok(None)
+    } else if who.eq_ignore_ascii_case("me") {
+        repo::git_user_email(repo_root)
+            .map(Some)
+            .ok_or_else(|| "'me' needs git user.email to be set".to_string())
+    } else {
+        Ok(Some(who.to_string()))
+    }
 }
 
-fn cmd_add(repo_root: &std::path::Path, location: &str, tag_str: &str, message: &str) -> ExitCode {
+fn cmd_add(
+    repo_root: &std::path::Path,
+    location: &str,
+    tag_str: &str,
+    message: &str,
+    assign: Option<&str>,
+) -> ExitCode {
     let (path, start, end) = match parse_location(location) {
         Ok(t) => t,
         Err(e) => {
@@ -157,6 +596,14 @@ fn cmd_add(repo_root: &std::path::Path, location: &str, tag_str: &str, message:
             return ExitCode::from(1);
         }
     };
+    let assignee = match assign.map(|who| resolve_assignee(repo_root, who)) {
+        Some(Ok(a)) => a,
+        Some(Err(e)) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+        None => None,
+    };
 
     let cfg = match config::load_config(repo_root) {
         Ok(c) => c,
@@ -182,28 +629,15 @@ fn cmd_add(repo_root: &std::path::Path, location: &str, tag_str: &str, message:
         }
     };
 
-    let author_resolved = author::resolve_author(repo_root, &path, start, end);
-    let created_at = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let expires_at = config::expires_days(&cfg, tag).map(|days| {
-        let d = chrono::Local::now() + chrono::Duration::days(days as i64);
-        d.format("%Y-%m-%d").to_string()
-    });
-
-    let id = reports_list.next_id();
-    let entry = reports::ReportEntry {
-        id: id.clone(),
-        path: path.clone(),
-        range: reports::LineRange { start, end },
-        tag: tag.as_str().to_string(),
-        message: message.to_string(),
-        author: reports::Author {
-            git: author_resolved.git,
-            codeowner: author_resolved.codeowner,
-        },
-        created_at,
-        expires_at,
-        status: "open".to_string(),
+    let id = reports_list.new_id(cfg.id_scheme, &format!("{}\n{}", path, message));
+    let mut entry = match new_entry(repo_root, &cfg, &id, &path, start, end, tag, message) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
     };
+    entry.assignee = assignee;
     reports_list.add_entry(entry);
 
     match reports::save_reports(repo_root, &reports_list) {
@@ -218,11 +652,78 @@ fn cmd_add(repo_root: &std::path::Path, location: &str, tag_str: &str, message:
     }
 }
 
-fn cmd_list(
+/// Build a new open report: resolves ownership and computes expiry from the tag config.
+/// Errors when the tag's expiry is out of range.
+#[allow(clippy::too_many_arguments)]
+fn new_entry(
     repo_root: &std::path::Path,
-    tag_filter: Option<&str>,
-    status_filter: Option<&str>,
-) -> ExitCode {
+    cfg: &config::Config,
+    id: &str,
+    path: &str,
+    start: u32,
+    end: u32,
+    tag: String,
+    message: &str,
+) -> Result<reports::ReportEntry, String> {
+    let author_resolved = author::resolve_author(repo_root, path, start, end);
+    let today = chrono::Local::now().date_naive();
+    let created_at = today.format("%Y-%m-%d").to_string();
+    let expires_at = config::expires_at(cfg, &tag, today)?;
+    Ok(reports::ReportEntry {
+        id: id.to_string(),
+        path: path.to_string(),
+        range: reports::LineRange { start, end },
+        tag,
+        message: message.to_string(),
+        author: reports::Author {
+            git: author_resolved.git,
+            codeowner: author_resolved.codeowner,
+            owners: author_resolved.owners,
+        },
+        assignee: None,
+        created_at,
+        expires_at,
+        status: "open".to_string(),
+        anchor: std::fs::read_to_string(repo_root.join(path))
+            .ok()
+            .and_then(|text| anchor::compute(&text, start, end)),
+        commit: repo::head_commit(repo_root),
+        orphaned: false,
+        history: vec![],
+        comments: vec![],
+    })
+}
+
+fn query_context(repo_root: &std::path::Path) -> query::Context {
+    query::Context {
+        today: check::today(),
+        me: repo::git_user_email(repo_root),
+    }
+}
+
+fn cmd_list(repo_root: &std::path::Path, args: &ListArgs) -> ExitCode {
+    let filter = match args.filter.to_query() {
+        Ok(q) => q,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let sort_keys = match args.sort.as_deref().map(query::SortKey::parse_list) {
+        Some(Ok(keys)) => keys,
+        Some(Err(e)) => {
+            eprintln!("error: --sort: {}", e);
+            return ExitCode::from(1);
+        }
+        None => Vec::new(),
+    };
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
     let reports_list = match reports::load_reports(repo_root) {
         Ok(r) => r,
         Err(e) => {
@@ -231,17 +732,21 @@ fn cmd_list(
         }
     };
 
-    let entries = reports_list.entries.iter().filter(|e| {
-        let tag_ok = tag_filter
-            .map(|t| e.tag.eq_ignore_ascii_case(t))
-            .unwrap_or(true);
-        let status_ok = status_filter
-            .map(|s| e.status.eq_ignore_ascii_case(s))
-            .unwrap_or(true);
-        tag_ok && status_ok
-    });
+    let ctx = query_context(repo_root);
+    let mut items: Vec<check::Evaluation> = check::evaluate_all(&cfg, &reports_list, ctx.today)
+        .into_iter()
+        .filter(|ev| filter.as_ref().map(|q| q.matches(ev, &ctx)).unwrap_or(true))
+        .collect();
+    query::sort(&mut items, &sort_keys);
+    if let Some(limit) = args.limit {
+        items.truncate(limit);
+    }
 
-    for e in entries {
+    if args.format != output::Format::Table {
+        return print_rendered(args.format, &items);
+    }
+    for ev in &items {
+        let e = ev.entry;
         let range = format!("{}-{}", e.range.start, e.range.end);
         println!(
             "{}  {}  {}  {}  {}  {}",
@@ -260,13 +765,16 @@ fn cmd_delete(repo_root: &std::path::Path, id: &str) -> ExitCode {
         }
     };
 
-    if !reports_list.delete_by_id(id) {
-        eprintln!("error: report not found: {}", id);
+    if let Err(e) = reports_list.delete_by_id(id) {
+        eprintln!("error: {}", e);
         return ExitCode::from(1);
     }
 
     match reports::save_reports(repo_root, &reports_list) {
         Ok(()) => {
+            if let Err(e) = baseline::forget_in_baseline(repo_root, id) {
+                eprintln!("warning: could not update baseline: {}", e);
+            }
             println!("Deleted {}", id);
             ExitCode::SUCCESS
         }
@@ -277,7 +785,14 @@ fn cmd_delete(repo_root: &std::path::Path, id: &str) -> ExitCode {
     }
 }
 
-fn cmd_resolve(repo_root: &std::path::Path, id: &str) -> ExitCode {
+fn cmd_edit(repo_root: &std::path::Path, args: &EditArgs) -> ExitCode {
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
     let mut reports_list = match reports::load_reports(repo_root) {
         Ok(r) => r,
         Err(e) => {
@@ -285,15 +800,58 @@ fn cmd_resolve(repo_root: &std::path::Path, id: &str) -> ExitCode {
             return ExitCode::from(1);
         }
     };
+    let actor = repo::git_user_email(repo_root);
+    let id = args.id.as_str();
+    let entry = match reports_list.find_mut(id) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
 
-    if !reports_list.resolve_by_id(id) {
-        eprintln!("error: report not found: {}", id);
-        return ExitCode::from(1);
+    let mut changes = match args.to_edit() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    if changes.is_empty() {
+        let edited = edit::to_editor_yaml(entry)
+            .and_then(|yaml| edit::run_editor(&yaml, id))
+            .and_then(|text| edit::from_editor_yaml(&text, entry));
+        changes = match edited {
+            Ok(Some(c)) => edit::Edit {
+                keep_expiry: args.keep_expiry,
+                ..c
+            },
+            Ok(None) => {
+                println!("Edit aborted");
+                return ExitCode::SUCCESS;
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::from(1);
+            }
+        };
     }
 
+    let source = std::fs::read_to_string(repo_root.join(&entry.path)).ok();
+    let changed = match changes.apply(&cfg, entry, source.as_deref(), actor) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}: {}", id, e);
+            return ExitCode::from(1);
+        }
+    };
+    if changed.is_empty() {
+        println!("No changes to {}", id);
+        return ExitCode::SUCCESS;
+    }
     match reports::save_reports(repo_root, &reports_list) {
         Ok(()) => {
-            println!("Resolved {}", id);
+            println!("Edited {} ({})", id, changed.join(", "));
             ExitCode::SUCCESS
         }
         Err(e) => {
@@ -303,7 +861,13 @@ fn cmd_resolve(repo_root: &std::path::Path, id: &str) -> ExitCode {
     }
 }
 
-fn cmd_check(repo_root: &std::path::Path) -> ExitCode {
+/// Change a report's status (validated against config `transitions`) and record it in history.
+fn cmd_transition(
+    repo_root: &std::path::Path,
+    id: &str,
+    to: reports::Status,
+    note: Option<String>,
+) -> ExitCode {
     let cfg = match config::load_config(repo_root) {
         Ok(c) => c,
         Err(e) => {
@@ -311,7 +875,7 @@ fn cmd_check(repo_root: &std::path::Path) -> ExitCode {
             return ExitCode::from(1);
         }
     };
-    let reports_list = match reports::load_reports(repo_root) {
+    let mut reports_list = match reports::load_reports(repo_root) {
         Ok(r) => r,
         Err(e) => {
             eprintln!("error: {}", e);
@@ -319,54 +883,1200 @@ fn cmd_check(repo_root: &std::path::Path) -> ExitCode {
         }
     };
 
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let mut violations = Vec::new();
-    for e in &reports_list.entries {
-        if e.status != "open" {
-            continue;
+    let actor = repo::git_user_email(repo_root);
+    let entry = match reports_list.find_mut(id) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
         }
-        let tag = match config::Tag::from_str(e.tag.as_str()) {
-            Ok(t) => t,
-            _ => continue,
-        };
-        let severity = match config::severity(&cfg, tag) {
-            Ok(s) => s,
-            _ => continue,
-        };
-        let blocking = severity == config::Severity::Blocking;
-        let expired = e
-            .expires_at
-            .as_ref()
-            .map(|d| d.as_str() < today.as_str())
-            .unwrap_or(false);
-        if blocking || expired {
-            violations.push((
-                e.id.as_str(),
-                e.path.as_str(),
-                e.tag.as_str(),
-                e.message.as_str(),
-            ));
+    };
+    if let Err(e) = config::validate_transition(&cfg, &entry.status, to) {
+        eprintln!("error: {}: {}", id, e);
+        return ExitCode::from(1);
+    }
+    entry.transition(to, actor, note);
+
+    if let Err(e) = reports::save_reports(repo_root, &reports_list) {
+        eprintln!("error: {}", e);
+        return ExitCode::from(1);
+    }
+    if !to.is_active() {
+        if let Err(e) = baseline::forget_in_baseline(repo_root, id) {
+            eprintln!("warning: could not update baseline: {}", e);
         }
     }
+    let verb = match to {
+        reports::Status::Open => "Opened",
+        reports::Status::InProgress => "Started",
+        reports::Status::Resolved => "Resolved",
+        reports::Status::Wontfix => "Closed as wontfix",
+        reports::Status::Duplicate => "Closed as duplicate",
+        reports::Status::Reopened => "Reopened",
+    };
+    println!("{} {}", verb, id);
+    ExitCode::SUCCESS
+}
 
-    if violations.is_empty() {
-        return ExitCode::SUCCESS;
+fn cmd_assign(repo_root: &std::path::Path, id: &str, who: &str) -> ExitCode {
+    let assignee = match resolve_assignee(repo_root, who) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let actor = repo::git_user_email(repo_root);
+    let entry = match reports_list.find_mut(id) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let change = edit::Edit {
+        assignee: Some(assignee.clone()),
+        ..edit::Edit::default()
+    };
+    if let Err(e) = change.apply(&cfg, entry, None, actor) {
+        eprintln!("error: {}: {}", id, e);
+        return ExitCode::from(1);
     }
-    for (id, path, tag, message) in &violations {
-        eprintln!("{}  {}  {}  {}", id, path, tag, message);
+
+    match reports::save_reports(repo_root, &reports_list) {
+        Ok(()) => {
+            match assignee {
+                Some(who) => println!("Assigned {} to {}", id, who),
+                None => println!("Unassigned {}", id),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(1)
+        }
     }
-    ExitCode::from(1)
 }
 
-fn cmd_html(repo_root: &std::path::Path, no_open: bool) -> ExitCode {
-    let reports_list = match reports::load_reports(repo_root) {
+fn cmd_bulk(repo_root: &std::path::Path, command: BulkCommand) -> ExitCode {
+    let clearable = |v: Option<String>, clear: &str| {
+        v.map(|s| {
+            let s = s.trim();
+            (!s.is_empty() && !s.eq_ignore_ascii_case(clear)).then(|| s.to_string())
+        })
+    };
+    let (select, action, verb) = match command {
+        BulkCommand::Resolve {
+            select,
+            reason,
+            close_as,
+        } => {
+            let to = close_as.status();
+            let verb = match to {
+                reports::Status::Resolved => ("resolve", "Resolved"),
+                reports::Status::Wontfix => ("close as wontfix", "Closed as wontfix"),
+                _ => ("close as duplicate", "Closed as duplicate"),
+            };
+            let note = reason.filter(|r| !r.trim().is_empty());
+            (select, bulk::Action::Transition { to, note }, verb)
+        }
+        BulkCommand::Delete { select } => (select, bulk::Action::Delete, ("delete", "Deleted")),
+        BulkCommand::Edit {
+            select,
+            set_message,
+            set_tag,
+            set_expires,
+            set_assignee,
+            keep_expiry,
+        } => {
+            let change = edit::Edit {
+                message: set_message,
+                tag: set_tag,
+                range: None,
+                expires_at: clearable(set_expires, "never"),
+                assignee: clearable(set_assignee, "none"),
+                keep_expiry,
+            };
+            if change.is_empty() {
+                eprintln!("error: nothing to change (use --set-message, --set-tag, --set-expires or --set-assignee)");
+                return ExitCode::from(1);
+            }
+            (select, bulk::Action::Edit(change), ("edit", "Edited"))
+        }
+        BulkCommand::Assign { who, select } => {
+            let assignee = match resolve_assignee(repo_root, &who) {
+                Ok(a) => a,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    return ExitCode::from(1);
+                }
+            };
+            let change = edit::Edit {
+                assignee: Some(assignee),
+                ..edit::Edit::default()
+            };
+            (select, bulk::Action::Edit(change), ("assign", "Assigned"))
+        }
+    };
+
+    let filter = match select.filter.to_query() {
+        Ok(Some(q)) => q,
+        Ok(None) => {
+            eprintln!(
+                "error: give at least one filter (--path, --tag, --status, --assignee or --query)"
+            );
+            return ExitCode::from(1);
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
         Ok(r) => r,
         Err(e) => {
             eprintln!("error: {}", e);
             return ExitCode::from(1);
         }
     };
-    let index_path = match html::generate_html(repo_root, &reports_list) {
+
+    let ctx = query_context(repo_root);
+    let selected: Vec<usize> = reports_list
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| filter.matches(&check::evaluate(&cfg, e, ctx.today), &ctx))
+        .map(|(i, _)| i)
+        .collect();
+    let actor = repo::git_user_email(repo_root);
+    let plan = bulk::plan(&cfg, repo_root, &reports_list, &selected, &action, actor);
+
+    if selected.is_empty() {
+        println!("No reports match the filter.");
+        return ExitCode::SUCCESS;
+    }
+
+    let (verb, done) = verb;
+    let mut listing = String::new();
+    for p in &plan.changes {
+        let e = &reports_list.entries[p.index];
+        let status = if p.updated.status != e.status {
+            format!("{} -> {}", e.status, p.updated.status)
+        } else {
+            e.status.clone()
+        };
+        listing.push_str(&format!("  {}  {}  {}  {}", e.id, e.path, e.tag, status));
+        if !p.changed.is_empty() {
+            listing.push_str(&format!("  ({})", p.changed.join(", ")));
+        }
+        listing.push('\n');
+    }
+    if !plan.skipped.is_empty() {
+        listing.push_str(&format!("Skipped {}:\n", plan.skipped.len()));
+        for (id, why) in &plan.skipped {
+            listing.push_str(&format!("  {}: {}\n", id, why));
+        }
+    }
+    if !select.yes || plan.changes.is_empty() {
+        println!("Would {} {} report(s):", verb, plan.changes.len());
+        print!("{}", listing);
+        if !plan.changes.is_empty() {
+            println!("Dry run: nothing was changed. Re-run with --yes to apply.");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let forget: Vec<String> = plan
+        .changes
+        .iter()
+        .filter(|p| match action {
+            bulk::Action::Delete => true,
+            _ => !p.updated.is_active(),
+        })
+        .map(|p| p.updated.id.clone())
+        .collect();
+    let changed_count = plan.changes.len();
+    bulk::apply(&mut reports_list, plan, &action);
+    if let Err(e) = reports::save_reports(repo_root, &reports_list) {
+        eprintln!("error: {}", e);
+        return ExitCode::from(1);
+    }
+    let forget: Vec<&str> = forget.iter().map(String::as_str).collect();
+    if let Err(e) = baseline::forget_all_in_baseline(repo_root, &forget) {
+        eprintln!("warning: could not update baseline: {}", e);
+    }
+    println!("{} {} report(s):", done, changed_count);
+    print!("{}", listing);
+    ExitCode::SUCCESS
+}
+
+fn cmd_owners_refresh(
+    repo_root: &std::path::Path,
+    filter: &FilterArgs,
+    blame: bool,
+    dry_run: bool,
+    clear: bool,
+) -> ExitCode {
+    let filter = match filter.to_query() {
+        Ok(q) => q,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let ctx = query_context(repo_root);
+    let selected: Vec<usize> = reports_list
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.is_active())
+        .filter(|(_, e)| {
+            filter
+                .as_ref()
+                .is_none_or(|q| q.matches(&check::evaluate(&cfg, e, ctx.today), &ctx))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    let codeowners = codeowners::CodeOwners::load(repo_root);
+    if codeowners.is_none() {
+        if !clear {
+            eprintln!("error: no CODEOWNERS file found; pass --clear to remove the owners of the selected reports");
+            return ExitCode::from(1);
+        }
+        eprintln!("warning: no CODEOWNERS file found; owners will be cleared");
+    }
+    let refreshed = owners::refresh(
+        repo_root,
+        codeowners.as_ref(),
+        &reports_list,
+        &selected,
+        blame,
+    );
+    if refreshed.is_empty() {
+        println!(
+            "Owners are up to date ({} open report(s) checked)",
+            selected.len()
+        );
+        return ExitCode::SUCCESS;
+    }
+    for r in &refreshed {
+        let e = &reports_list.entries[r.index];
+        println!("{}  {}", e.id, e.path);
+        for (field, from, to) in r.changes() {
+            println!(
+                "  {}: {} -> {}",
+                field,
+                from.as_deref().unwrap_or("(none)"),
+                to.as_deref().unwrap_or("(none)")
+            );
+        }
+        if let Some(ref c) = codeowners {
+            for m in &r.rules {
+                println!("  rule: {}", m.describe(&c.file));
+            }
+        }
+    }
+    let count = refreshed.len();
+    if dry_run {
+        println!("Would update {} report(s)", count);
+        return ExitCode::SUCCESS;
+    }
+    let actor = repo::git_user_email(repo_root);
+    owners::apply(&mut reports_list, refreshed, actor);
+    if let Err(e) = reports::save_reports(repo_root, &reports_list) {
+        eprintln!("error: {}", e);
+        return ExitCode::from(1);
+    }
+    println!("Updated {} report(s)", count);
+    ExitCode::SUCCESS
+}
+
+fn cmd_extend(repo_root: &std::path::Path, id: &str, days: u32, reason: &str) -> ExitCode {
+    if reason.trim().is_empty() {
+        eprintln!("error: --reason is required");
+        return ExitCode::from(1);
+    }
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let actor = repo::git_user_email(repo_root);
+    let entry = match reports_list.find_mut(id) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    if !entry.is_active() {
+        eprintln!("error: {} is {}; reopen it first", id, entry.status);
+        return ExitCode::from(1);
+    }
+    let (count, total_days) = entry.extensions();
+    let limits = total_days
+        .checked_add(days)
+        .ok_or_else(|| "too many days of extensions".to_string())
+        .and_then(|total| config::check_extension_limits(&cfg, &entry.tag, count + 1, total));
+    if let Err(e) = limits {
+        eprintln!("error: {}: {}", id, e);
+        return ExitCode::from(1);
+    }
+    let to = match entry.extend(days, check::today(), actor, reason.trim()) {
+        Ok(to) => to,
+        Err(e) => {
+            eprintln!("error: {}: {}", id, e);
+            return ExitCode::from(1);
+        }
+    };
+
+    match reports::save_reports(repo_root, &reports_list) {
+        Ok(()) => {
+            println!("Extended {} to {}", id, to);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn cmd_comment(repo_root: &std::path::Path, id: &str, text: &str) -> ExitCode {
+    if text.trim().is_empty() {
+        eprintln!("error: comment is empty");
+        return ExitCode::from(1);
+    }
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let author = repo::git_user_email(repo_root);
+    let entry = match reports_list.find_mut(id) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    entry.add_comment(author, text);
+
+    match reports::save_reports(repo_root, &reports_list) {
+        Ok(()) => {
+            println!("Commented on {}", id);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn cmd_show(repo_root: &std::path::Path, id: &str, no_color: bool) -> ExitCode {
+    use std::io::IsTerminal;
+
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let entry = match reports_list.find(id) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let item = check::evaluate(&cfg, entry, check::today());
+    let source = std::fs::read_to_string(repo_root.join(&entry.path)).ok();
+    let blame = author::blame_authors(repo_root, &entry.path, entry.range.start, entry.range.end);
+    let codeowners = codeowners::CodeOwners::load(repo_root);
+    let color =
+        !no_color && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+    print!(
+        "{}",
+        show::render(
+            &item,
+            &show::Extras {
+                source: source.as_deref(),
+                blame: &blame,
+                codeowners: codeowners.as_ref(),
+                color,
+            },
+        )
+    );
+    ExitCode::SUCCESS
+}
+
+fn cmd_search(repo_root: &std::path::Path, query: &str, limit: usize, no_color: bool) -> ExitCode {
+    use std::io::IsTerminal;
+
+    let reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let hits = search::search_reports(repo_root, &reports_list, query);
+    if hits.is_empty() {
+        println!("No reports match '{}'.", query);
+        return ExitCode::SUCCESS;
+    }
+    let color =
+        !no_color && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+    for hit in hits.iter().take(limit) {
+        print!("{}", search::render(hit, color));
+    }
+    if hits.len() > limit {
+        println!("... {} more (use --limit)", hits.len() - limit);
+    }
+    ExitCode::SUCCESS
+}
+
+fn cmd_check(repo_root: &std::path::Path, args: &CheckArgs) -> ExitCode {
+    let filter = match args.query.as_deref().map(query::Query::parse) {
+        Some(Ok(q)) => Some(q),
+        Some(Err(e)) => {
+            eprintln!("error: --query: {}", e);
+            return ExitCode::from(1);
+        }
+        None => None,
+    };
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let changes = anchor::relocate_reports(repo_root, &mut reports_list);
+    if !changes.is_empty() {
+        if let Err(e) = reports::save_reports(repo_root, &reports_list) {
+            eprintln!("warning: could not save relocated reports: {}", e);
+        }
+    }
+    for e in reports_list
+        .entries
+        .iter()
+        .filter(|e| e.orphaned && e.is_active())
+    {
+        eprintln!(
+            "warning: {} is orphaned: code not found in {}",
+            e.id, e.path
+        );
+    }
+    if args.stale_owners {
+        match codeowners::CodeOwners::load(repo_root) {
+            Some(c) => {
+                for (id, owner) in owners::stale_owners(&c, &reports_list) {
+                    eprintln!(
+                        "warning: {}: owner {} no longer appears in {} (run `codereport owners refresh`)",
+                        id, owner, c.file
+                    );
+                }
+            }
+            None => eprintln!("warning: --stale-owners: no CODEOWNERS file found"),
+        }
+    }
+
+    let changed = match args.base.as_deref() {
+        Some(base) => match changes::changed_lines(repo_root, base, args.worktree) {
+            Ok(c) => Some(c),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::from(1);
+            }
+        },
+        None => None,
+    };
+    let ctx = query_context(repo_root);
+    let in_scope = |ev: &check::Evaluation| {
+        changed
+            .as_ref()
+            .map(|c| c.overlaps(&ev.entry.path, &ev.entry.range))
+            .unwrap_or(true)
+            && filter.as_ref().map(|q| q.matches(ev, &ctx)).unwrap_or(true)
+    };
+    // --guard (only with --ratchet): changing the lines of an open blocking report fails as
+    // well, even when the report is in the baseline.
+    let guarded = |ev: &check::Evaluation| args.guard && ev.is_open() && ev.is_blocking();
+
+    let evaluations = check::evaluate_all(&cfg, &reports_list, check::today());
+    let mut failed = evaluations
+        .iter()
+        .any(|ev| in_scope(ev) && ev.is_violation());
+
+    // Ratchet: only violations missing from the baseline (or tag growth) fail.
+    let mut new_ids: Option<std::collections::HashSet<String>> = None;
+    if args.ratchet {
+        let mut bl = match baseline::load_baseline(repo_root) {
+            Ok(Some(b)) => b,
+            Ok(None) => {
+                eprintln!("error: no baseline found. Run 'codereport baseline' first.");
+                return ExitCode::from(1);
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::from(1);
+            }
+        };
+        // Renumberings left by the merge driver count even when the baseline is not saved.
+        let renumbered = baseline::apply_deferred(repo_root, &mut bl);
+        let all_failing: Vec<&check::Evaluation> =
+            evaluations.iter().filter(|ev| ev.is_violation()).collect();
+        let ratchet = bl.ratchet(&all_failing);
+        // A scoped check (--base, --query) only looks at part of the store, so it never
+        // shrinks the baseline on disk; a full `check --ratchet` does that.
+        let scoped = changed.is_some() || filter.is_some();
+        if (renumbered || !ratchet.pruned.is_empty()) && !scoped {
+            if !ratchet.pruned.is_empty() {
+                eprintln!(
+                    "Baseline shrunk: {} no longer violating ({})",
+                    ratchet.pruned.len(),
+                    ratchet.pruned.join(", ")
+                );
+            }
+            if let Err(e) = baseline::save_baseline(repo_root, &bl) {
+                eprintln!("warning: could not save baseline: {}", e);
+            }
+        }
+        // Tag counts are repo-wide, so they are not compared in a scoped check.
+        let increased = if changed.is_some() || filter.is_some() {
+            Vec::new()
+        } else {
+            ratchet.increased
+        };
+        for (tag, before, now) in &increased {
+            eprintln!(
+                "error: '{}' violations went up from {} to {}",
+                tag, before, now
+            );
+        }
+        failed = !increased.is_empty()
+            || evaluations
+                .iter()
+                .any(|ev| in_scope(ev) && (ratchet.new_ids.contains(&ev.entry.id) || guarded(ev)));
+        new_ids = Some(ratchet.new_ids);
+    }
+    let is_new = |ev: &check::Evaluation| {
+        new_ids
+            .as_ref()
+            .map(|ids| ids.contains(&ev.entry.id))
+            .unwrap_or(true)
+    };
+
+    let items: Vec<check::Evaluation> = evaluations
+        .iter()
+        .filter(|ev| in_scope(ev))
+        .filter(|ev| {
+            if args.all || args.format.includes_all_open() {
+                ev.is_open()
+            } else {
+                (ev.is_violation() && is_new(ev)) || guarded(ev)
+            }
+        })
+        .cloned()
+        .collect();
+
+    let text = match output::render_check(args.format, &items) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    if let Some(ref path) = args.output {
+        if let Err(e) = std::fs::write(path, &text) {
+            eprintln!("error: write {}: {}", path.display(), e);
+            return ExitCode::from(1);
+        }
+    } else if args.format == output::CheckFormat::Table {
+        eprint!("{}", text);
+    } else {
+        print!("{}", text);
+    }
+
+    if failed {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn cmd_baseline(repo_root: &std::path::Path) -> ExitCode {
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let evaluations = check::evaluate_all(&cfg, &reports_list, check::today());
+    let violations: Vec<&check::Evaluation> =
+        evaluations.iter().filter(|ev| ev.is_violation()).collect();
+    let bl = baseline::Baseline::from_violations(&violations);
+    if let Err(e) = baseline::save_baseline(repo_root, &bl) {
+        eprintln!("error: {}", e);
+        return ExitCode::from(1);
+    }
+    let counts: Vec<String> = bl
+        .counts
+        .iter()
+        .map(|(tag, n)| format!("{}: {}", tag, n))
+        .collect();
+    println!(
+        "Baseline recorded: {} violations ({})",
+        bl.entries.len(),
+        counts.join(", ")
+    );
+    ExitCode::SUCCESS
+}
+
+/// Print evaluations in a machine-readable format to stdout.
+fn print_rendered(format: output::Format, items: &[check::Evaluation]) -> ExitCode {
+    match output::render(format, items) {
+        Ok(Some(text)) => {
+            print!("{}", text);
+            ExitCode::SUCCESS
+        }
+        Ok(None) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn cmd_relocate(repo_root: &std::path::Path, dry_run: bool) -> ExitCode {
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let changes = anchor::relocate_reports(repo_root, &mut reports_list);
+    for c in &changes {
+        match &c.kind {
+            anchor::ChangeKind::Moved { from, to } => println!(
+                "{}  {}  {}-{} -> {}-{}",
+                c.id, c.path, from.start, from.end, to.start, to.end
+            ),
+            anchor::ChangeKind::Orphaned => println!("{}  {}  orphaned", c.id, c.path),
+            anchor::ChangeKind::Recovered { range } => println!(
+                "{}  {}  found again at {}-{}",
+                c.id, c.path, range.start, range.end
+            ),
+            anchor::ChangeKind::Anchored => println!("{}  {}  anchored", c.id, c.path),
+        }
+    }
+
+    if changes.is_empty() {
+        println!("All reports in place");
+        return ExitCode::SUCCESS;
+    }
+    if dry_run {
+        return ExitCode::SUCCESS;
+    }
+    match reports::save_reports(repo_root, &reports_list) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn cmd_sync(repo_root: &std::path::Path, dry_run: bool) -> ExitCode {
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let changes = match sync::sync_paths(repo_root, &mut reports_list) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut renamed = 0usize;
+    let mut deleted = 0usize;
+    for c in &changes {
+        match c {
+            sync::SyncChange::Renamed { id, from, to } => {
+                renamed += 1;
+                println!("{}  {} -> {}", id, from, to);
+            }
+            sync::SyncChange::Deleted { id, path } => {
+                deleted += 1;
+                println!("{}  {}  deleted", id, path);
+            }
+            sync::SyncChange::Stamped { .. } => {}
+            sync::SyncChange::UnknownCommit { id, commit } => {
+                eprintln!("warning: {}: commit {} not found; skipped", id, commit);
+            }
+        }
+    }
+    println!("{} renamed, {} deleted", renamed, deleted);
+
+    if changes.is_empty() || dry_run {
+        return ExitCode::SUCCESS;
+    }
+    match reports::save_reports(repo_root, &reports_list) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn cmd_scan(repo_root: &std::path::Path, dry_run: bool) -> ExitCode {
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    if cfg.scan.markers.is_empty() {
+        eprintln!("warning: no scan markers are configured; set scan.markers in config.yaml");
+    }
+    let markers = match scan::scan_repo(repo_root, &cfg.scan) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    // Markers already imported are matched by (path, message), counted so that repeated
+    // identical comments in one file stay idempotent; a report at exactly the marker's
+    // line also counts as tracked.
+    let mut existing: std::collections::HashMap<(String, String), usize> =
+        std::collections::HashMap::new();
+    for e in &reports_list.entries {
+        *existing
+            .entry((e.path.clone(), e.message.clone()))
+            .or_insert(0) += 1;
+    }
+    let mut added = 0usize;
+    let mut tracked = 0usize;
+    for m in &markers {
+        if let Some(n) = existing.get_mut(&(m.path.clone(), m.message.clone())) {
+            if *n > 0 {
+                *n -= 1;
+                tracked += 1;
+                continue;
+            }
+        }
+        let at_line = reports_list
+            .entries
+            .iter()
+            .any(|e| e.path == m.path && e.range.start == m.line && e.range.end == m.line);
+        if at_line {
+            tracked += 1;
+            continue;
+        }
+        let tag = match config::validate_tag_for_add(&cfg, &m.tag) {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("warning: {}:{}: {}", m.path, m.line, e);
+                continue;
+            }
+        };
+        let id = reports_list.new_id(cfg.id_scheme, &format!("{}\n{}", m.path, m.message));
+        let entry = match new_entry(
+            repo_root, &cfg, &id, &m.path, m.line, m.line, tag, &m.message,
+        ) {
+            Ok(e) => e,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::from(1);
+            }
+        };
+        if dry_run {
+            println!(
+                "Would add {} {}:{} {} {}",
+                id, m.path, m.line, entry.tag, m.message
+            );
+        } else {
+            println!("Added {} {}:{}", id, m.path, m.line);
+        }
+        reports_list.add_entry(entry);
+        added += 1;
+    }
+
+    if !dry_run && added > 0 {
+        if let Err(e) = reports::save_reports(repo_root, &reports_list) {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    }
+    println!(
+        "Found {} markers: {} new, {} already tracked",
+        markers.len(),
+        added,
+        tracked
+    );
+    ExitCode::SUCCESS
+}
+
+fn cmd_migrate(repo_root: &std::path::Path, dry_run: bool) -> ExitCode {
+    type Validate = fn(&str) -> Result<(), String>;
+    let files: [(&str, &'static [migrate::Step], u32, Validate); 2] = [
+        (
+            "config.yaml",
+            migrate::CONFIG_STEPS,
+            config::CONFIG_VERSION,
+            |text| config::parse_config(text).map(|_| ()),
+        ),
+        (
+            "reports.yaml",
+            migrate::REPORTS_STEPS,
+            reports::REPORTS_VERSION,
+            |text| reports::parse_reports(text).map(|_| ()),
+        ),
+    ];
+    let dir = repo_root.join(".codereports");
+    let mut failed = false;
+    for (name, steps, target, validate) in files {
+        let path = dir.join(name);
+        if !path.exists() {
+            continue;
+        }
+        if dry_run {
+            let version = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|c| serde_yaml::from_str::<serde_yaml::Value>(&c).ok())
+                .map(|doc| migrate::version_of(&doc))
+                .unwrap_or(target);
+            if version >= target {
+                println!("{}: version {}, up to date", name, version);
+                continue;
+            }
+            println!("{}: version {} -> {}", name, version, target);
+            for step in migrate::pending(steps, version, target) {
+                println!("  v{} -> v{}: {}", step.from, step.from + 1, step.summary);
+            }
+            continue;
+        }
+        match migrate::migrate_file(&path, steps, target, validate) {
+            Ok(Some((from, backup))) => println!(
+                "Migrated {} from version {} to {} (backup: {})",
+                name,
+                from,
+                target,
+                backup.strip_prefix(repo_root).unwrap_or(&backup).display()
+            ),
+            Ok(None) => println!("{}: version {}, up to date", name, target),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn cmd_doctor(repo_root: &std::path::Path, fix: bool) -> ExitCode {
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let problems = doctor::diagnose(&cfg, repo_root, &reports_list);
+    if problems.is_empty() {
+        println!(
+            "No problems found in {} report(s)",
+            reports_list.entries.len()
+        );
+        return ExitCode::SUCCESS;
+    }
+    for p in &problems {
+        match (&p.fix, fix) {
+            (Some(f), true) => println!("{}: {} (fixed: {})", p.id, p.message, f.describe()),
+            (Some(f), false) => println!("{}: {} (--fix: {})", p.id, p.message, f.describe()),
+            (None, _) => println!("{}: {}", p.id, p.message),
+        }
+    }
+    let affected: std::collections::HashSet<usize> = problems.iter().map(|p| p.index).collect();
+    let fixable = problems.iter().filter(|p| p.fix.is_some()).count();
+    let unfixable = problems.len() - fixable;
+    println!();
+    println!(
+        "{} problem(s) in {} report(s)",
+        problems.len(),
+        affected.len()
+    );
+    if !fix {
+        if fixable > 0 {
+            println!("{} can be repaired with `codereport doctor --fix`", fixable);
+        }
+        return ExitCode::from(1);
+    }
+    if fixable > 0 {
+        let actor = repo::git_user_email(repo_root);
+        let renumbered = doctor::repair(&cfg, &mut reports_list, &problems, actor);
+        if let Err(e) = reports::save_reports(repo_root, &reports_list) {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+        if let Err(e) = baseline::renumber_in_baseline(repo_root, &reports_list, &renumbered) {
+            eprintln!("warning: could not update baseline: {}", e);
+        }
+        for (from, to) in &renumbered {
+            println!("Renumbered {} -> {}", from, to);
+        }
+        println!("Fixed {}", fixable);
+    }
+    if unfixable > 0 {
+        println!("{} need(s) fixing by hand (`codereport edit`)", unfixable);
+        return ExitCode::from(1);
+    }
+    ExitCode::SUCCESS
+}
+
+fn cmd_fix_ids(repo_root: &std::path::Path, dry_run: bool) -> ExitCode {
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let actor = repo::git_user_email(repo_root);
+    let changed = reports_list.fix_duplicate_ids(cfg.id_scheme, actor);
+    if changed.is_empty() {
+        println!("No duplicate IDs");
+        return ExitCode::SUCCESS;
+    }
+    let verb = if dry_run {
+        "Would renumber"
+    } else {
+        "Renumbered"
+    };
+    for (from, to) in &changed {
+        let entry = reports_list.entries.iter().find(|e| e.id == *to);
+        let path = entry.map(|e| e.path.as_str()).unwrap_or("");
+        println!("{} {} -> {}  {}", verb, from, to, path);
+    }
+    if dry_run {
+        return ExitCode::SUCCESS;
+    }
+    if let Err(e) = reports::save_reports(repo_root, &reports_list) {
+        eprintln!("error: {}", e);
+        return ExitCode::from(1);
+    }
+    if let Err(e) = baseline::renumber_in_baseline(repo_root, &reports_list, &changed) {
+        eprintln!("warning: could not update baseline: {}", e);
+    }
+    ExitCode::SUCCESS
+}
+
+fn cmd_merge_driver(
+    base: &std::path::Path,
+    ours: &std::path::Path,
+    theirs: &std::path::Path,
+) -> ExitCode {
+    let read = |path: &std::path::Path| {
+        std::fs::read_to_string(path)
+            .map_err(|e| format!("read {}: {}", path.display(), e))
+            .and_then(|content| reports::parse_reports(&content))
+    };
+    let (base_reports, our_reports, their_reports) = match (read(base), read(ours), read(theirs)) {
+        (Ok(b), Ok(o), Ok(t)) => (b, o, t),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            eprintln!("codereport merge-driver: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    // Git runs drivers from the top of the work tree, so the config is usually at hand.
+    let repo_root = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| repo::find_repo_root(&cwd));
+    let scheme = repo_root
+        .as_ref()
+        .and_then(|root| config::load_config(root).ok())
+        .map(|cfg| cfg.id_scheme)
+        .unwrap_or_default();
+    let outcome = merge::merge(&base_reports, &our_reports, &their_reports, scheme);
+    let yaml = match serde_yaml::to_string(&outcome.reports) {
+        Ok(y) => y,
+        Err(e) => {
+            eprintln!("codereport merge-driver: serialize reports: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    if let Err(e) = std::fs::write(ours, yaml) {
+        eprintln!("codereport merge-driver: write {}: {}", ours.display(), e);
+        return ExitCode::from(1);
+    }
+    for (from, to) in &outcome.renumbered {
+        eprintln!(
+            "codereport: renumbered {} -> {} (ID added on both branches)",
+            from, to
+        );
+    }
+    if let (Some(root), false) = (&repo_root, outcome.renumbered.is_empty()) {
+        if let Err(e) = baseline::defer_renumbering(root, &outcome.reports, &outcome.renumbered) {
+            eprintln!("codereport merge-driver: {}", e);
+        }
+    }
+    if outcome.conflicts.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+    for c in &outcome.conflicts {
+        eprintln!("codereport: conflict: {} (kept our side)", c);
+    }
+    ExitCode::from(1)
+}
+
+fn cmd_html(repo_root: &std::path::Path, no_open: bool, query_str: Option<&str>) -> ExitCode {
+    let filter = match query_str.map(query::Query::parse) {
+        Some(Ok(q)) => Some(q),
+        Some(Err(e)) => {
+            eprintln!("error: --query: {}", e);
+            return ExitCode::from(1);
+        }
+        None => None,
+    };
+    let cfg = match config::load_config(repo_root) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let mut reports_list = match reports::load_reports(repo_root) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    if let Some(ref q) = filter {
+        let ctx = query_context(repo_root);
+        let keep: std::collections::HashSet<String> =
+            check::evaluate_all(&cfg, &reports_list, ctx.today)
+                .iter()
+                .filter(|ev| q.matches(ev, &ctx))
+                .map(|ev| ev.entry.id.clone())
+                .collect();
+        reports_list.entries.retain(|e| keep.contains(&e.id));
+    }
+    let index_path = match html::generate_html(repo_root, &cfg, &reports_list) {
         Ok(p) => p,
         Err(e) => {
             eprintln!("error: {}", e);
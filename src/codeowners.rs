@@ -0,0 +1,461 @@
+use std::path::{Path, PathBuf};
+
+use crate::glob::Glob;
+
+/// Where CODEOWNERS is looked for, in order; the first file found is used. GitHub reads
+/// `.github/`, the root and `docs/`; GitLab also reads `.gitlab/`. `.git/CODEOWNERS` is
+/// still read for repositories set up before the others were supported.
+pub const LOCATIONS: [&str; 5] = [
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+    ".git/CODEOWNERS",
+];
+
+/// A gitignore-style CODEOWNERS pattern.
+#[derive(Debug, Clone)]
+struct Pattern {
+    glob: Glob,
+    /// Trailing `/`: only matches directories (i.e. paths below it).
+    dir_only: bool,
+    /// Whether a match on a directory covers the files below it. False for `dir/*`,
+    /// which GitHub documents as not matching nested files.
+    descend: bool,
+}
+
+impl Pattern {
+    /// `None` for patterns CODEOWNERS does not support (`!negation`, empty).
+    fn new(raw: &str) -> Option<Pattern> {
+        if raw.starts_with('!') {
+            return None;
+        }
+        let dir_only = raw.ends_with('/');
+        let trimmed = raw.trim_end_matches('/');
+        // A slash at the start or in the middle anchors the pattern to the root;
+        // otherwise it matches at any depth.
+        let anchored = trimmed.contains('/');
+        let body = trimmed.trim_start_matches('/');
+        if body.is_empty() {
+            return None;
+        }
+        let source = if anchored {
+            body.to_string()
+        } else {
+            format!("**/{}", body)
+        };
+        Some(Pattern {
+            glob: Glob::new(&source),
+            dir_only,
+            descend: !(anchored && (body == "*" || body.ends_with("/*"))),
+        })
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        if !self.dir_only && self.glob.matches(path) {
+            return true;
+        }
+        if !self.descend && !self.dir_only {
+            return false;
+        }
+        path.match_indices('/')
+            .any(|(i, _)| self.glob.matches(&path[..i]))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: String,
+    matcher: Pattern,
+    owners: Vec<String>,
+    line: usize,
+}
+
+/// A GitLab `[Section]`; the rules before the first header form an unnamed section.
+#[derive(Debug, Clone)]
+struct Section {
+    name: Option<String>,
+    optional: bool,
+    approvals: u32,
+    rules: Vec<Rule>,
+}
+
+/// The rule that decided ownership of a path within one section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub section: Option<String>,
+    /// `^[Section]`: approval from these owners is optional.
+    pub optional: bool,
+    /// `[Section][N]`: approvals required (1 by default).
+    pub approvals: u32,
+    pub pattern: String,
+    /// 1-based line in the CODEOWNERS file.
+    pub line: usize,
+    pub owners: Vec<String>,
+}
+
+impl Match {
+    /// One line for display, e.g. `CODEOWNERS:12 /src/ @a @b [Security, optional, 2 approvals]`.
+    pub fn describe(&self, file: &str) -> String {
+        let owners = if self.owners.is_empty() {
+            "(no owners)".to_string()
+        } else {
+            self.owners.join(" ")
+        };
+        let mut out = format!("{}:{} {} {}", file, self.line, self.pattern, owners);
+        let mut section: Vec<String> = self.section.iter().cloned().collect();
+        if self.optional {
+            section.push("optional".to_string());
+        }
+        if self.approvals != 1 {
+            section.push(format!("{} approvals", self.approvals));
+        }
+        if !section.is_empty() {
+            out.push_str(&format!(" [{}]", section.join(", ")));
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeOwners {
+    /// The file the rules came from, relative to the repository root.
+    pub file: String,
+    sections: Vec<Section>,
+}
+
+/// Split a line on unescaped whitespace, keeping escapes (`\ `) for the glob.
+fn tokens(line: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    out.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        out.push(current);
+    }
+    out
+}
+
+/// Owners up to an inline `# comment`.
+fn owners(tokens: &[String]) -> Vec<String> {
+    tokens
+        .iter()
+        .take_while(|t| !t.starts_with('#'))
+        .cloned()
+        .collect()
+}
+
+/// A GitLab section header: `[Name]`, `^[Name]`, `[Name][2]`, optionally followed by
+/// default owners. Returns (name, optional, approvals, default owners).
+fn section_header(line: &str) -> Option<(String, bool, u32, Vec<String>)> {
+    let (optional, rest) = match line.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let rest = rest.strip_prefix('[')?;
+    let close = rest.find(']')?;
+    let name = rest[..close].trim().to_string();
+    let mut rest = &rest[close + 1..];
+    let mut approvals = 1;
+    if let Some(count) = rest.strip_prefix('[') {
+        let close = count.find(']')?;
+        approvals = count[..close].trim().parse().ok()?;
+        rest = &count[close + 1..];
+    }
+    // `[abc]*.rs @x` is a pattern, not a header.
+    if name.is_empty() || !(rest.is_empty() || rest.starts_with(char::is_whitespace)) {
+        return None;
+    }
+    Some((name, optional, approvals, owners(&tokens(rest))))
+}
+
+impl CodeOwners {
+    pub fn parse(file: &str, content: &str) -> CodeOwners {
+        let mut sections = vec![Section {
+            name: None,
+            optional: false,
+            approvals: 1,
+            rules: Vec::new(),
+        }];
+        let mut current = 0;
+        let mut defaults: Vec<String> = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((name, optional, approvals, default_owners)) = section_header(line) {
+                // GitLab combines sections with the same name (case-insensitive).
+                current = match sections.iter().position(|s| {
+                    s.name
+                        .as_deref()
+                        .is_some_and(|n| n.eq_ignore_ascii_case(&name))
+                }) {
+                    Some(index) => index,
+                    None => {
+                        sections.push(Section {
+                            name: Some(name),
+                            optional,
+                            approvals,
+                            rules: Vec::new(),
+                        });
+                        sections.len() - 1
+                    }
+                };
+                defaults = default_owners;
+                continue;
+            }
+            let tokens = tokens(line);
+            let Some(matcher) = Pattern::new(&tokens[0]) else {
+                continue;
+            };
+            let mut rule_owners = owners(&tokens[1..]);
+            if rule_owners.is_empty() {
+                rule_owners = defaults.clone();
+            }
+            sections[current].rules.push(Rule {
+                pattern: tokens[0].clone(),
+                matcher,
+                owners: rule_owners,
+                line: i + 1,
+            });
+        }
+        CodeOwners {
+            file: file.to_string(),
+            sections,
+        }
+    }
+
+    /// Read the first CODEOWNERS file found in `LOCATIONS`.
+    pub fn load(repo_root: &Path) -> Option<CodeOwners> {
+        let (file, path) = locate(repo_root)?;
+        let content = std::fs::read_to_string(path).ok()?;
+        Some(CodeOwners::parse(file, &content))
+    }
+
+    /// The last matching rule of each section, in file order. A rule with no owners
+    /// still matches: it leaves the path without owners in that section.
+    pub fn matches(&self, path: &str) -> Vec<Match> {
+        let path = path.replace('\\', "/");
+        let path = path.trim_start_matches('/');
+        self.sections
+            .iter()
+            .filter_map(|section| {
+                let rule = section
+                    .rules
+                    .iter()
+                    .rev()
+                    .find(|r| r.matcher.matches(path))?;
+                Some(Match {
+                    section: section.name.clone(),
+                    optional: section.optional,
+                    approvals: section.approvals,
+                    pattern: rule.pattern.clone(),
+                    line: rule.line,
+                    owners: rule.owners.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// True if any rule lists `owner`, directly or through its section's default owners.
+    pub fn mentions(&self, owner: &str) -> bool {
+        self.sections
+            .iter()
+            .flat_map(|s| &s.rules)
+            .any(|r| r.owners.iter().any(|o| o.eq_ignore_ascii_case(owner)))
+    }
+
+    /// Everyone who owns `path`: owners from required sections first, then optional
+    /// ones, each once.
+    pub fn owners_for(&self, path: &str) -> Vec<String> {
+        let matches = self.matches(path);
+        let mut all: Vec<String> = Vec::new();
+        for m in matches
+            .iter()
+            .filter(|m| !m.optional)
+            .chain(matches.iter().filter(|m| m.optional))
+        {
+            for owner in &m.owners {
+                if !all.contains(owner) {
+                    all.push(owner.clone());
+                }
+            }
+        }
+        all
+    }
+}
+
+/// The CODEOWNERS file in use: (location from `LOCATIONS`, full path).
+pub fn locate(repo_root: &Path) -> Option<(&'static str, PathBuf)> {
+    LOCATIONS
+        .iter()
+        .map(|file| (*file, repo_root.join(file)))
+        .find(|(_, path)| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners(content: &str, path: &str) -> Vec<String> {
+        CodeOwners::parse("CODEOWNERS", content).owners_for(path)
+    }
+
+    #[test]
+    fn github_pattern_semantics() {
+        // (pattern, path, matches)
+        let cases = [
+            ("*", "a/b/c.rs", true),
+            ("*.rs", "main.rs", true),
+            ("*.rs", "src/deep/main.rs", true),
+            ("*.rs", "main.rsx", false),
+            ("/*.rs", "main.rs", true),
+            ("/*.rs", "src/main.rs", false),
+            ("docs/**/*.md", "docs/a.md", true),
+            ("docs/**/*.md", "docs/x/y/a.md", true),
+            ("docs/**/*.md", "src/docs/a.md", false),
+            ("/src/*/mod.rs", "src/net/mod.rs", true),
+            ("/src/*/mod.rs", "src/net/tcp/mod.rs", false),
+            ("/build/logs/", "build/logs/a/b.log", true),
+            ("/build/logs/", "x/build/logs/a.log", false),
+            ("/build/logs/", "build/logs", false),
+            ("apps/", "apps/web/index.js", true),
+            ("apps/", "x/apps/web/index.js", true),
+            ("apps/", "apps", false),
+            ("docs/*", "docs/getting-started.md", true),
+            ("docs/*", "docs/build-app/troubleshooting.md", false),
+            ("/docs/", "docs/a/b.md", true),
+            ("**/logs", "build/logs/a.log", true),
+            ("**/logs", "deeply/nested/logs/a.log", true),
+            ("**/logs", "logs", true),
+            ("logs", "a/logs/b.log", true),
+            ("logs", "a/logs", true),
+            ("logs", "a/logs.txt", false),
+            ("/src/lib.rs", "src/lib.rs", true),
+            ("src/lib.rs", "x/src/lib.rs", false),
+            ("file\\ name.txt", "dir/file name.txt", true),
+            ("\\#notes", "#notes", true),
+            ("!*.rs", "main.rs", false),
+        ];
+        for (pattern, path, expected) in cases {
+            let content = format!("{} @owner\n", pattern);
+            assert_eq!(
+                !owners(&content, path).is_empty(),
+                expected,
+                "{} vs {}",
+                pattern,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn last_match_wins_and_keeps_every_owner() {
+        let content = "\
+# default owners
+*       @global-owner1 @global-owner2
+*.js    @js-owner # inline comment
+/build/logs/ @doctocat
+/apps/  @octocat
+/apps/github
+";
+        assert_eq!(
+            owners(content, "README.md"),
+            vec!["@global-owner1", "@global-owner2"]
+        );
+        assert_eq!(owners(content, "src/app.js"), vec!["@js-owner"]);
+        assert_eq!(owners(content, "build/logs/x.js"), vec!["@doctocat"]);
+        assert_eq!(owners(content, "apps/web/x.js"), vec!["@octocat"]);
+        // A later rule without owners makes the path unowned.
+        assert!(owners(content, "apps/github/x.js").is_empty());
+    }
+
+    #[test]
+    fn gitlab_sections() {
+        let content = "\
+* @fallback
+
+[Documentation] @docs-team
+docs/
+README.md @writer
+
+^[Security][2] @sec-a @sec-b
+*.rs
+
+[Backend]
+/src/ @backend
+/src/ui/ @frontend
+
+[documentation]
+*.md
+";
+        let parsed = CodeOwners::parse("CODEOWNERS", content);
+        let m = parsed.matches("src/auth.rs");
+        let names: Vec<Option<&str>> = m.iter().map(|m| m.section.as_deref()).collect();
+        assert_eq!(names, vec![None, Some("Security"), Some("Backend")]);
+        assert!(m[1].optional);
+        assert_eq!(m[1].approvals, 2);
+        assert_eq!(m[2].approvals, 1);
+        assert_eq!(
+            m[1].describe(&parsed.file),
+            "CODEOWNERS:8 *.rs @sec-a @sec-b [Security, optional, 2 approvals]"
+        );
+        assert_eq!(
+            m[2].describe(&parsed.file),
+            "CODEOWNERS:11 /src/ @backend [Backend]"
+        );
+        // Required sections first, optional ones after.
+        assert_eq!(
+            parsed.owners_for("src/auth.rs"),
+            vec!["@fallback", "@backend", "@sec-a", "@sec-b"]
+        );
+        assert_eq!(
+            parsed.owners_for("src/ui/view.rs"),
+            vec!["@fallback", "@frontend", "@sec-a", "@sec-b"]
+        );
+        // Same-named sections are combined; the later `*.md` rule (with the default
+        // owners of its header, none) wins over `README.md @writer`.
+        let docs = parsed.matches("README.md");
+        assert_eq!(docs[1].pattern, "*.md");
+        assert_eq!(docs[1].line, 15);
+        assert_eq!(
+            parsed.owners_for("docs/guide.txt"),
+            vec!["@fallback", "@docs-team"]
+        );
+        // `[abc]` followed directly by more pattern is a pattern, not a header.
+        assert_eq!(owners("[ab]*.rs @x\n", "a1.rs"), vec!["@x"]);
+    }
+
+    #[test]
+    fn finds_the_file_in_each_location() {
+        let dir =
+            std::env::temp_dir().join(format!("codereport-codeowners-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(CodeOwners::load(&dir).is_none());
+        for (i, file) in LOCATIONS.iter().enumerate().rev() {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, format!("* @owner{}\n", i)).unwrap();
+            let loaded = CodeOwners::load(&dir).unwrap();
+            assert_eq!(loaded.file, *file);
+            assert_eq!(loaded.owners_for("a.rs"), vec![format!("@owner{}", i)]);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
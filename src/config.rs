@@ -1,49 +1,19 @@
+use crate::migrate;
+use crate::reports::Status;
 use std::collections::HashMap;
 use std::path::Path;
 use std::str::FromStr;
 
-const CONFIG_VERSION: u32 = 1;
-
-/// Fixed tag set; unknown tags are rejected.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum Tag {
-    Todo,
-    Refactor,
-    Buggy,
-    Critical,
-}
-
-impl Tag {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Tag::Todo => "todo",
-            Tag::Refactor => "refactor",
-            Tag::Buggy => "buggy",
-            Tag::Critical => "critical",
-        }
-    }
-}
+pub const CONFIG_VERSION: u32 = 1;
 
 fn to_ascii_lowercase(s: &str) -> String {
     s.chars().flat_map(|c| c.to_lowercase()).collect()
 }
 
-impl FromStr for Tag {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match to_ascii_lowercase(s).as_str() {
-            "todo" => Ok(Tag::Todo),
-            "refactor" => Ok(Tag::Refactor),
-            "buggy" => Ok(Tag::Buggy),
-            "critical" => Ok(Tag::Critical),
-            _ => Err(format!("unknown tag: {}", s)),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// Ordered from least to most severe.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Low,
@@ -66,18 +36,163 @@ impl FromStr for Severity {
     }
 }
 
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Blocking => "blocking",
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TagConfig {
     pub enabled: bool,
     pub severity: String,
     #[serde(default)]
     pub expires: Option<u32>,
+    /// Display colour for the dashboard, as `#rgb` or `#rrggbb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// How many times `extend` may push back a report's expiry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_extensions: Option<u32>,
+    /// Upper bound on the days added by all extensions of one report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_days: Option<u32>,
+}
+
+/// Settings for `codereport scan`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScanConfig {
+    /// Comment marker (case-sensitive, e.g. "TODO") -> tag it is imported as.
+    #[serde(default = "default_scan_markers")]
+    pub markers: HashMap<String, String>,
+    /// File extension (or exact file name) -> comment prefixes for that language.
+    #[serde(default = "default_scan_comments")]
+    pub comments: HashMap<String, Vec<String>>,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        ScanConfig {
+            markers: default_scan_markers(),
+            comments: default_scan_comments(),
+        }
+    }
+}
+
+fn default_scan_markers() -> HashMap<String, String> {
+    [("TODO", "todo"), ("FIXME", "buggy"), ("XXX", "critical")]
+        .into_iter()
+        .map(|(m, t)| (m.to_string(), t.to_string()))
+        .collect()
+}
+
+fn default_scan_comments() -> HashMap<String, Vec<String>> {
+    let slash = &["//", "/*"][..];
+    let hash = &["#"][..];
+    let table: &[(&[&str], &[&str])] = &[
+        (
+            &[
+                "rs", "c", "h", "cc", "cpp", "hpp", "go", "java", "js", "jsx", "ts", "tsx", "kt",
+                "swift", "scala", "cs", "php", "dart", "proto",
+            ],
+            slash,
+        ),
+        (
+            &[
+                "py",
+                "rb",
+                "sh",
+                "bash",
+                "zsh",
+                "yaml",
+                "yml",
+                "toml",
+                "pl",
+                "r",
+                "ex",
+                "exs",
+                "Dockerfile",
+                "Makefile",
+            ],
+            hash,
+        ),
+        (&["sql", "lua", "hs"], &["--"]),
+        (&["html", "xml", "md", "vue", "svelte"], &["<!--", "//"]),
+        (&["css", "scss", "less"], &["/*", "//"]),
+    ];
+    let mut comments = HashMap::new();
+    for (exts, prefixes) in table {
+        for ext in *exts {
+            comments.insert(
+                ext.to_string(),
+                prefixes.iter().map(|p| p.to_string()).collect(),
+            );
+        }
+    }
+    comments
+}
+
+/// How `add` and `scan` allocate report IDs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdScheme {
+    /// CR-000001, CR-000002, ... (max + 1; collides across branches)
+    #[default]
+    Sequential,
+    /// CR- plus a short hash of the report and its creation time, e.g. CR-3f9a1c2e
+    Hash,
+    /// CR- plus a ULID (time-ordered, random), e.g. CR-01J9Z3K4X8QW5T7N2M6B0C1D4E
+    Ulid,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Config {
     pub version: u32,
+    /// Scheme for new report IDs.
+    #[serde(default)]
+    pub id_scheme: IdScheme,
     pub tags: HashMap<String, TagConfig>,
+    #[serde(default)]
+    pub scan: ScanConfig,
+    /// Allowed status changes: status -> statuses it may move to.
+    #[serde(default = "default_transitions")]
+    pub transitions: HashMap<String, Vec<String>>,
+}
+
+fn default_transitions() -> HashMap<String, Vec<String>> {
+    let table: &[(&str, &[&str])] = &[
+        ("open", &["in_progress", "resolved", "wontfix", "duplicate"]),
+        ("in_progress", &["open", "resolved", "wontfix", "duplicate"]),
+        (
+            "reopened",
+            &["in_progress", "resolved", "wontfix", "duplicate"],
+        ),
+        ("resolved", &["reopened"]),
+        ("wontfix", &["reopened"]),
+        ("duplicate", &["reopened"]),
+    ];
+    table
+        .iter()
+        .map(|(from, to)| (from.to_string(), to.iter().map(|t| t.to_string()).collect()))
+        .collect()
+}
+
+impl Config {
+    /// Look up a tag by name (case-insensitive). Returns the configured name and its config.
+    pub fn tag(&self, name: &str) -> Option<(&str, &TagConfig)> {
+        if let Some((k, tc)) = self.tags.get_key_value(name) {
+            return Some((k.as_str(), tc));
+        }
+        self.tags
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(k, tc)| (k.as_str(), tc))
+    }
 }
 
 pub fn load_config(repo_root: &Path) -> Result<Config, String> {
@@ -89,18 +204,93 @@ pub fn load_config(repo_root: &Path) -> Result<Config, String> {
             format!("failed to read config: {}", e)
         }
     })?;
-    let config: Config =
-        serde_yaml::from_str(&content).map_err(|e| format!("invalid config.yaml: {}", e))?;
-    if config.version != CONFIG_VERSION {
-        return Err(format!(
-            "unsupported config version: {} (expected {})",
-            config.version, CONFIG_VERSION
-        ));
+    let (config, version) = parse_config(&content)?;
+    if version < CONFIG_VERSION {
+        eprintln!(
+            "warning: config.yaml is version {} (current is {}); run `codereport migrate` to upgrade it",
+            version, CONFIG_VERSION
+        );
     }
+    Ok(config)
+}
+
+/// Parse and validate the content of a config.yaml, upgrading older versions in memory.
+/// Also returns the version the content was written in.
+pub fn parse_config(content: &str) -> Result<(Config, u32), String> {
+    let mut doc: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|e| format!("invalid config.yaml: {}", e))?;
+    let version = migrate::upgrade(
+        &mut doc,
+        migrate::CONFIG_STEPS,
+        CONFIG_VERSION,
+        "config.yaml",
+    )?;
+    let explicit_markers = doc
+        .get("scan")
+        .and_then(|scan| scan.get("markers"))
+        .is_some();
+    let mut config: Config =
+        serde_yaml::from_value(doc).map_err(|e| format!("invalid config.yaml: {}", e))?;
     for (name, tc) in &config.tags {
+        if !is_valid_tag_name(name) {
+            return Err(format!(
+                "invalid tag name '{}' (use letters, digits, '-' or '_')",
+                name
+            ));
+        }
         Severity::from_str(&tc.severity).map_err(|e| format!("tag '{}': {}", name, e))?;
+        if let Some(ref color) = tc.color {
+            if !is_valid_color(color) {
+                return Err(format!(
+                    "tag '{}': invalid color '{}' (expected #rgb or #rrggbb)",
+                    name, color
+                ));
+            }
+        }
+    }
+    for (from, targets) in &config.transitions {
+        Status::from_str(from).map_err(|e| format!("transitions: {}", e))?;
+        for to in targets {
+            Status::from_str(to).map_err(|e| format!("transitions.{}: {}", from, e))?;
+        }
+    }
+    if explicit_markers {
+        let mut markers: Vec<(&String, &String)> = config.scan.markers.iter().collect();
+        markers.sort();
+        for (marker, tag) in markers {
+            if config.tag(tag).is_none() {
+                return Err(format!(
+                    "scan marker '{}': tag '{}' is not defined in config",
+                    marker, tag
+                ));
+            }
+        }
+    } else {
+        // The default markers assume the default tags; drop those a custom tag set lacks.
+        let tags = config.tags.clone();
+        config
+            .scan
+            .markers
+            .retain(|_, tag| tags.keys().any(|k| k.eq_ignore_ascii_case(tag)));
+    }
+    Ok((config, version))
+}
+
+/// Tag names end up in CLI output and CSS class names, so keep them simple.
+fn is_valid_tag_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_valid_color(color: &str) -> bool {
+    match color.strip_prefix('#') {
+        Some(hex) => {
+            (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => false,
     }
-    Ok(config)
 }
 
 /// Default config matching the spec (todo, refactor, buggy, critical with expires).
@@ -112,6 +302,9 @@ pub fn default_config() -> Config {
             enabled: true,
             severity: "low".to_string(),
             expires: None,
+            color: Some("#6b7280".to_string()),
+            max_extensions: None,
+            max_total_days: None,
         },
     );
     tags.insert(
@@ -120,6 +313,9 @@ pub fn default_config() -> Config {
             enabled: true,
             severity: "medium".to_string(),
             expires: Some(180),
+            color: Some("#8b5cf6".to_string()),
+            max_extensions: None,
+            max_total_days: None,
         },
     );
     tags.insert(
@@ -128,6 +324,9 @@ pub fn default_config() -> Config {
             enabled: true,
             severity: "high".to_string(),
             expires: Some(90),
+            color: Some("#f59e0b".to_string()),
+            max_extensions: None,
+            max_total_days: None,
         },
     );
     tags.insert(
@@ -136,40 +335,108 @@ pub fn default_config() -> Config {
             enabled: true,
             severity: "blocking".to_string(),
             expires: Some(14),
+            color: Some("#ef4444".to_string()),
+            max_extensions: Some(2),
+            max_total_days: Some(14),
         },
     );
     Config {
         version: CONFIG_VERSION,
+        id_scheme: IdScheme::default(),
         tags,
+        scan: ScanConfig::default(),
+        transitions: default_transitions(),
     }
 }
 
-/// Validate tag for add: must be known (Tag enum) and present in config with enabled true.
-pub fn validate_tag_for_add(config: &Config, tag_str: &str) -> Result<Tag, String> {
-    let tag = Tag::from_str(tag_str)?;
-    let key = tag.as_str();
-    let tc = config
-        .tags
-        .get(key)
-        .ok_or_else(|| format!("tag '{}' is not defined in config", key))?;
+/// Validate tag for add: must be defined in config with enabled true.
+/// Returns the tag name as spelled in config.
+pub fn validate_tag_for_add(config: &Config, tag_str: &str) -> Result<String, String> {
+    let (name, tc) = config
+        .tag(tag_str.trim())
+        .ok_or_else(|| format!("tag '{}' is not defined in config", tag_str))?;
     if !tc.enabled {
-        return Err(format!("tag '{}' is disabled in config", key));
+        return Err(format!("tag '{}' is disabled in config", name));
     }
-    Ok(tag)
+    Ok(name.to_string())
 }
 
 /// Get expiration days for a tag from config.
-pub fn expires_days(config: &Config, tag: Tag) -> Option<u32> {
-    config.tags.get(tag.as_str()).and_then(|tc| tc.expires)
+pub fn expires_days(config: &Config, tag: &str) -> Option<u32> {
+    config.tag(tag).and_then(|(_, tc)| tc.expires)
+}
+
+/// `expires_at` for a report with `tag` created on `from`, or `None` if the tag never expires.
+/// Errors when the tag's `expires` is too large to be a date.
+pub fn expires_at(
+    config: &Config,
+    tag: &str,
+    from: chrono::NaiveDate,
+) -> Result<Option<String>, String> {
+    let Some(days) = expires_days(config, tag) else {
+        return Ok(None);
+    };
+    from.checked_add_signed(chrono::Duration::days(days as i64))
+        .map(|d| Some(d.format("%Y-%m-%d").to_string()))
+        .ok_or_else(|| format!("tag '{}': expires: {} days is out of range", tag, days))
+}
+
+/// Check `count` extensions adding `total_days` against the tag's `max_extensions` /
+/// `max_total_days`. Tags without limits (or unknown tags) always pass.
+pub fn check_extension_limits(
+    config: &Config,
+    tag: &str,
+    count: u32,
+    total_days: u32,
+) -> Result<(), String> {
+    let Some((name, tc)) = config.tag(tag) else {
+        return Ok(());
+    };
+    if let Some(max) = tc.max_extensions {
+        if count > max {
+            return Err(format!(
+                "{} extensions exceed the limit of {} for '{}'",
+                count, max, name
+            ));
+        }
+    }
+    if let Some(max) = tc.max_total_days {
+        if total_days > max {
+            return Err(format!(
+                "extended by {} days in total, over the limit of {} for '{}'",
+                total_days, max, name
+            ));
+        }
+    }
+    Ok(())
 }
 
 /// Get severity for a tag from config.
-pub fn severity(config: &Config, tag: Tag) -> Result<Severity, String> {
+pub fn severity(config: &Config, tag: &str) -> Result<Severity, String> {
     config
-        .tags
-        .get(tag.as_str())
-        .ok_or_else(|| format!("tag '{}' not in config", tag.as_str()))
-        .and_then(|tc| Severity::from_str(&tc.severity))
+        .tag(tag)
+        .ok_or_else(|| format!("tag '{}' not in config", tag))
+        .and_then(|(_, tc)| Severity::from_str(&tc.severity))
+}
+
+/// Check that `from -> to` is allowed by `transitions` in config.
+pub fn validate_transition(config: &Config, from: &str, to: Status) -> Result<(), String> {
+    let from_status = Status::from_str(from)?;
+    let allowed = config
+        .transitions
+        .iter()
+        .find(|(k, _)| Status::from_str(k).ok() == Some(from_status))
+        .map(|(_, targets)| targets.iter().any(|t| Status::from_str(t).ok() == Some(to)))
+        .unwrap_or(false);
+    if allowed {
+        Ok(())
+    } else {
+        Err(format!(
+            "transition {} -> {} is not allowed by config",
+            from_status.as_str(),
+            to.as_str()
+        ))
+    }
 }
 
 pub fn write_default_config(repo_root: &Path) -> Result<(), String> {
@@ -180,3 +447,85 @@ pub fn write_default_config(repo_root: &Path) -> Result<(), String> {
     std::fs::write(&path, yaml).map_err(|e| format!("write config: {}", e))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_tags_from_config() {
+        let mut cfg = default_config();
+        cfg.tags.insert(
+            "security".to_string(),
+            TagConfig {
+                enabled: true,
+                severity: "blocking".to_string(),
+                expires: Some(7),
+                color: Some("#dc2626".to_string()),
+                max_extensions: Some(1),
+                max_total_days: None,
+            },
+        );
+        cfg.tags.insert(
+            "a11y".to_string(),
+            TagConfig {
+                enabled: false,
+                severity: "medium".to_string(),
+                expires: None,
+                color: None,
+                max_extensions: None,
+                max_total_days: None,
+            },
+        );
+        assert_eq!(validate_tag_for_add(&cfg, "Security").unwrap(), "security");
+        assert_eq!(severity(&cfg, "security"), Ok(Severity::Blocking));
+        assert_eq!(expires_days(&cfg, "security"), Some(7));
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert_eq!(
+            expires_at(&cfg, "security", day),
+            Ok(Some("2026-01-08".to_string()))
+        );
+        assert_eq!(expires_at(&cfg, "a11y", day), Ok(None));
+        cfg.tags.get_mut("security").unwrap().expires = Some(u32::MAX);
+        assert!(expires_at(&cfg, "security", day).is_err());
+        assert!(check_extension_limits(&cfg, "security", 1, 30).is_ok());
+        assert!(check_extension_limits(&cfg, "security", 2, 30).is_err());
+        assert!(check_extension_limits(&cfg, "critical", 1, 15).is_err());
+        assert!(validate_tag_for_add(&cfg, "a11y").is_err());
+        assert!(validate_tag_for_add(&cfg, "perf").is_err());
+    }
+
+    #[test]
+    fn default_scan_markers_only_for_defined_tags() {
+        let only_note = "version: 1\ntags:\n  note:\n    enabled: true\n    severity: low\n";
+        let (cfg, _) = parse_config(only_note).unwrap();
+        assert!(cfg.scan.markers.is_empty());
+
+        let explicit = format!(
+            "{}scan:\n  markers:\n    NOTE: note\n    TODO: todo\n",
+            only_note
+        );
+        assert_eq!(
+            parse_config(&explicit).unwrap_err(),
+            "scan marker 'TODO': tag 'todo' is not defined in config"
+        );
+    }
+
+    #[test]
+    fn default_transitions_allow_lifecycle() {
+        let cfg = default_config();
+        assert!(validate_transition(&cfg, "open", Status::InProgress).is_ok());
+        assert!(validate_transition(&cfg, "in_progress", Status::Resolved).is_ok());
+        assert!(validate_transition(&cfg, "resolved", Status::Reopened).is_ok());
+        assert!(validate_transition(&cfg, "resolved", Status::Wontfix).is_err());
+        assert!(validate_transition(&cfg, "open", Status::Reopened).is_err());
+    }
+
+    #[test]
+    fn color_validation() {
+        assert!(is_valid_color("#fff"));
+        assert!(is_valid_color("#8b5cf6"));
+        assert!(!is_valid_color("8b5cf6"));
+        assert!(!is_valid_color("#8b5cf6;}"));
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::anchor::{self, Relocation};
+use crate::config::Config;
+use crate::reports::{self, LineRange, ReportEntry, Reports, Status};
+
+/// A repair that is safe to apply without asking: it keeps what the entry meant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    /// Give the entry a new ID (every entry but the first of a duplicated ID).
+    Renumber,
+    Status(Status),
+    Tag(String),
+    CreatedAt(String),
+    ExpiresAt(String),
+    Range(LineRange),
+    /// Re-anchored range found through the entry's anchor.
+    Relocate(LineRange, reports::Anchor),
+    Orphan,
+}
+
+impl Fix {
+    pub fn describe(&self) -> String {
+        match self {
+            Fix::Renumber => "give it a new ID".to_string(),
+            Fix::Status(s) => format!("set status to {}", s.as_str()),
+            Fix::Tag(t) => format!("set tag to {}", t),
+            Fix::CreatedAt(d) => format!("set created_at to {}", d),
+            Fix::ExpiresAt(d) => format!("set expires_at to {}", d),
+            Fix::Range(r) | Fix::Relocate(r, _) => format!("set range to {}-{}", r.start, r.end),
+            Fix::Orphan => "mark it orphaned".to_string(),
+        }
+    }
+}
+
+/// One problem with one entry. `index` points into `reports.entries`, since the ID may
+/// be shared with other entries.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub index: usize,
+    pub id: String,
+    pub message: String,
+    pub fix: Option<Fix>,
+}
+
+/// A stored date, or the `YYYY-MM-DD` form of a date written some other unambiguous way
+/// (a full timestamp, `2026/01/05`, `2026-1-5`).
+fn normalize_date(s: &str) -> Option<String> {
+    let s = s.trim();
+    let day = s.get(..10).unwrap_or(s);
+    [s, day]
+        .into_iter()
+        .find_map(|candidate| {
+            ["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d"]
+                .into_iter()
+                .find_map(|f| chrono::NaiveDate::parse_from_str(candidate, f).ok())
+        })
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+fn check_date(field: &str, value: &str, problem: &mut impl FnMut(String, Option<Fix>)) {
+    match normalize_date(value) {
+        Some(d) if d == value => {}
+        Some(d) => {
+            let fix = if field == "created_at" {
+                Fix::CreatedAt(d)
+            } else {
+                Fix::ExpiresAt(d)
+            };
+            problem(
+                format!("{} '{}' is not YYYY-MM-DD", field, value),
+                Some(fix),
+            );
+        }
+        None => problem(format!("malformed {} date: '{}'", field, value), None),
+    }
+}
+
+/// The entry's own fields against the config: status, tag, dates, range.
+fn check_fields(cfg: &Config, entry: &ReportEntry, problem: &mut impl FnMut(String, Option<Fix>)) {
+    match Status::from_str(&entry.status) {
+        Ok(s) if s.as_str() == entry.status => {}
+        Ok(s) => problem(
+            format!("status '{}' is not in canonical form", entry.status),
+            Some(Fix::Status(s)),
+        ),
+        Err(_) => problem(format!("unknown status '{}'", entry.status), None),
+    }
+    match cfg.tag(&entry.tag) {
+        Some((name, _)) if name == entry.tag => {}
+        Some((name, _)) => problem(
+            format!(
+                "tag '{}' does not match config spelling '{}'",
+                entry.tag, name
+            ),
+            Some(Fix::Tag(name.to_string())),
+        ),
+        None => problem(format!("unknown tag '{}'", entry.tag), None),
+    }
+    check_date("created_at", &entry.created_at, problem);
+    if let Some(expires) = &entry.expires_at {
+        check_date("expires_at", expires, problem);
+    }
+    let LineRange { start, end } = entry.range;
+    if start == 0 || end < start {
+        let fix = (start > end && end > 0).then_some(Fix::Range(LineRange {
+            start: end,
+            end: start,
+        }));
+        problem(format!("invalid range {}-{}", start, end), fix);
+    }
+}
+
+/// The entry against the working tree. Only active reports are checked: a closed report
+/// may point at code that has since been deleted, and an orphaned one is already known
+/// to have lost its code.
+fn check_file(
+    repo_root: &Path,
+    entry: &ReportEntry,
+    problem: &mut impl FnMut(String, Option<Fix>),
+) {
+    let active = Status::from_str(&entry.status).is_ok_and(|s| s.is_active());
+    if !active || entry.orphaned {
+        return;
+    }
+    let Ok(text) = std::fs::read_to_string(repo_root.join(&entry.path)) else {
+        problem(format!("file not found: {}", entry.path), Some(Fix::Orphan));
+        return;
+    };
+    let lines = text.lines().count() as u32;
+    if entry.range.start == 0 || entry.range.end < entry.range.start || entry.range.end <= lines {
+        return;
+    }
+    let fix = entry
+        .anchor
+        .as_ref()
+        .and_then(|a| match anchor::relocate(&text, &entry.range, a) {
+            Relocation::Moved { range, anchor } => Some(Fix::Relocate(range, anchor)),
+            _ => None,
+        });
+    problem(
+        format!(
+            "range {}-{} is past the end of {} ({} lines)",
+            entry.range.start, entry.range.end, entry.path, lines
+        ),
+        fix,
+    );
+}
+
+/// Check every entry against the config and the working tree.
+pub fn diagnose(cfg: &Config, repo_root: &Path, reports: &Reports) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (index, entry) in reports.entries.iter().enumerate() {
+        let mut problem = |message: String, fix: Option<Fix>| {
+            problems.push(Problem {
+                index,
+                id: entry.id.clone(),
+                message,
+                fix,
+            })
+        };
+        if let Some(first) = seen.get(entry.id.as_str()) {
+            problem(
+                format!("duplicate ID (also used by entry #{})", first + 1),
+                Some(Fix::Renumber),
+            );
+        } else {
+            seen.insert(&entry.id, index);
+        }
+        check_fields(cfg, entry, &mut problem);
+        check_file(repo_root, entry, &mut problem);
+    }
+    problems
+}
+
+/// Apply the fixes of `problems` (from `diagnose` on the same reports). New IDs are
+/// assigned last so the indices stay valid. Returns the (old, new) ID pairs.
+pub fn repair(
+    cfg: &Config,
+    reports: &mut Reports,
+    problems: &[Problem],
+    actor: Option<String>,
+) -> Vec<(String, String)> {
+    let mut renumber = false;
+    for p in problems {
+        let Some(fix) = &p.fix else { continue };
+        let entry = &mut reports.entries[p.index];
+        match fix {
+            Fix::Renumber => renumber = true,
+            Fix::Status(s) => entry.status = s.as_str().to_string(),
+            Fix::Tag(t) => entry.tag = t.clone(),
+            Fix::CreatedAt(d) => entry.created_at = d.clone(),
+            Fix::ExpiresAt(d) => entry.expires_at = Some(d.clone()),
+            Fix::Range(r) => entry.range = r.clone(),
+            Fix::Relocate(r, a) => {
+                entry.range = r.clone();
+                entry.anchor = Some(a.clone());
+            }
+            Fix::Orphan => entry.orphaned = true,
+        }
+    }
+    if renumber {
+        reports.fix_duplicate_ids(cfg.id_scheme, actor)
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+
+    #[test]
+    fn finds_problems_and_repairs_the_safe_ones() {
+        let dir = std::env::temp_dir().join(format!("codereport-doctor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.rs"), "one\ntwo\nthree\n").unwrap();
+        let cfg = config::default_config();
+        let mut entries = vec![
+            ReportEntry::sample("CR-000001", "a.rs"),
+            ReportEntry::sample("CR-000001", "a.rs"),
+            ReportEntry::sample("CR-000002", "gone.rs"),
+            ReportEntry::sample("CR-000003", "a.rs"),
+            ReportEntry::sample("CR-000004", "a.rs"),
+            ReportEntry::sample("CR-000005", "gone.rs"),
+        ];
+        entries[1].status = "In-Progress".to_string();
+        entries[1].tag = "TODO".to_string();
+        entries[2].expires_at = Some("2026/03/01".to_string());
+        entries[3].range = LineRange { start: 9, end: 7 };
+        entries[4].status = "pending".to_string();
+        entries[4].tag = "nope".to_string();
+        entries[4].created_at = "soon".to_string();
+        entries[5].status = "resolved".to_string();
+        let mut reports = Reports {
+            version: 1,
+            entries,
+        };
+
+        let problems = diagnose(&cfg, &dir, &reports);
+        let found: Vec<(&str, &str, bool)> = problems
+            .iter()
+            .map(|p| (p.id.as_str(), p.message.as_str(), p.fix.is_some()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("CR-000001", "duplicate ID (also used by entry #1)", true),
+                (
+                    "CR-000001",
+                    "status 'In-Progress' is not in canonical form",
+                    true
+                ),
+                (
+                    "CR-000001",
+                    "tag 'TODO' does not match config spelling 'todo'",
+                    true
+                ),
+                (
+                    "CR-000002",
+                    "expires_at '2026/03/01' is not YYYY-MM-DD",
+                    true
+                ),
+                ("CR-000002", "file not found: gone.rs", true),
+                ("CR-000003", "invalid range 9-7", true),
+                ("CR-000004", "unknown status 'pending'", false),
+                ("CR-000004", "unknown tag 'nope'", false),
+                ("CR-000004", "malformed created_at date: 'soon'", false),
+            ]
+        );
+
+        let renumbered = repair(&cfg, &mut reports, &problems, None);
+        assert_eq!(
+            renumbered,
+            vec![("CR-000001".to_string(), "CR-000006".to_string())]
+        );
+        let remaining = diagnose(&cfg, &dir, &reports);
+        assert_eq!(remaining.len(), 4);
+        assert!(remaining
+            .iter()
+            .all(|p| p.id == "CR-000004" || p.id == "CR-000003"));
+        // The swapped range 7-9 is still past the end of the 3-line file.
+        assert!(remaining[0]
+            .message
+            .starts_with("range 7-9 is past the end"));
+        assert!(reports.entries[2].orphaned);
+        assert_eq!(reports.entries[2].expires_at.as_deref(), Some("2026-03-01"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,370 @@
+use crate::anchor;
+use crate::config::{self, Config};
+use crate::reports::{self, HistoryEvent, HistoryRecord, LineRange, ReportEntry};
+
+/// Field changes for one report. `None` leaves a field alone; for the optional fields
+/// `Some(None)` clears them.
+#[derive(Debug, Clone, Default)]
+pub struct Edit {
+    pub message: Option<String>,
+    pub tag: Option<String>,
+    pub range: Option<LineRange>,
+    pub expires_at: Option<Option<String>>,
+    pub assignee: Option<Option<String>>,
+    /// Keep `expires_at` when the tag changes instead of recomputing it from the new tag.
+    /// Not allowed past the new tag's default when that tag limits extensions.
+    pub keep_expiry: bool,
+}
+
+impl Edit {
+    pub fn is_empty(&self) -> bool {
+        self.message.is_none()
+            && self.tag.is_none()
+            && self.range.is_none()
+            && self.expires_at.is_none()
+            && self.assignee.is_none()
+    }
+
+    /// Validate and apply to `entry`, recording one history event per changed field.
+    /// `source` is the current content of the report's file, used to check a new range
+    /// and re-anchor it. Nothing is changed if validation fails. Returns the changed fields.
+    pub fn apply(
+        &self,
+        cfg: &Config,
+        entry: &mut ReportEntry,
+        source: Option<&str>,
+        actor: Option<String>,
+    ) -> Result<Vec<&'static str>, String> {
+        let message = match self.message {
+            Some(ref m) if m.trim().is_empty() => return Err("message is empty".to_string()),
+            Some(ref m) => Some(m.trim().to_string()),
+            None => None,
+        };
+        let tag = match self.tag {
+            Some(ref t) => Some(config::validate_tag_for_add(cfg, t)?),
+            None => None,
+        };
+        if let Some(ref r) = self.range {
+            if r.start == 0 || r.end < r.start {
+                return Err("invalid range (start >= 1, end >= start)".to_string());
+            }
+            if let Some(text) = source {
+                let lines = text.lines().count() as u32;
+                if r.end > lines {
+                    return Err(format!(
+                        "range {}-{} is past end of {} ({} lines)",
+                        r.start, r.end, entry.path, lines
+                    ));
+                }
+            }
+        }
+        if let Some(Some(ref date)) = self.expires_at {
+            if reports::parse_date(date).is_none() {
+                return Err(format!(
+                    "invalid expiry date '{}' (expected YYYY-MM-DD)",
+                    date
+                ));
+            }
+        }
+        let retagged = tag.as_deref().is_some_and(|t| t != entry.tag);
+        let limited_tag = tag.as_deref().unwrap_or(&entry.tag);
+        if let Some(ref new_expiry) = self.expires_at {
+            if pushes_back_limited_expiry(cfg, entry, tag.as_deref(), new_expiry)? {
+                return Err(format!(
+                    "tag '{}' limits extensions; use 'codereport extend' to push the expiry back",
+                    limited_tag
+                ));
+            }
+        } else if retagged
+            && self.keep_expiry
+            && pushes_back_limited_expiry(cfg, entry, tag.as_deref(), &entry.expires_at)?
+        {
+            return Err(format!(
+                "tag '{}' limits extensions; keeping the expiry would outlast its default, drop --keep-expiry",
+                limited_tag
+            ));
+        }
+
+        let mut events: Vec<(&'static str, Option<String>, Option<String>)> = Vec::new();
+        if let Some(message) = message {
+            events.push((
+                "message",
+                Some(entry.message.clone()),
+                Some(message.clone()),
+            ));
+            entry.message = message;
+        }
+        let mut expires_at = self.expires_at.clone();
+        if let Some(tag) = tag {
+            if tag != entry.tag && expires_at.is_none() && !self.keep_expiry {
+                let created = reports::parse_date(&entry.created_at)
+                    .unwrap_or_else(|| chrono::Local::now().date_naive());
+                expires_at = Some(config::expires_at(cfg, &tag, created)?);
+            }
+            events.push(("tag", Some(entry.tag.clone()), Some(tag.clone())));
+            entry.tag = tag;
+        }
+        if let Some(ref range) = self.range {
+            let fmt = |r: &LineRange| format!("{}-{}", r.start, r.end);
+            events.push(("range", Some(fmt(&entry.range)), Some(fmt(range))));
+            if *range != entry.range {
+                entry.range = range.clone();
+                if let Some(text) = source {
+                    entry.anchor = anchor::compute(text, range.start, range.end);
+                    entry.orphaned = false;
+                }
+            }
+        }
+        if let Some(expires_at) = expires_at {
+            events.push(("expires_at", entry.expires_at.clone(), expires_at.clone()));
+            entry.expires_at = expires_at;
+        }
+        if let Some(ref assignee) = self.assignee {
+            events.push(("assignee", entry.assignee.clone(), assignee.clone()));
+            entry.assignee = assignee.clone();
+        }
+
+        let mut changed = Vec::new();
+        for (field, from, to) in events.into_iter().filter(|(_, from, to)| from != to) {
+            changed.push(field);
+            entry.history.push(HistoryRecord {
+                at: reports::now_rfc3339(),
+                actor: actor.clone(),
+                event: HistoryEvent::Edit {
+                    field: field.to_string(),
+                    from,
+                    to,
+                },
+                note: None,
+            });
+        }
+        Ok(changed)
+    }
+}
+
+/// For a tag with `max_extensions` / `max_total_days`, an expiry may only be pushed back
+/// through `extend`: true if `new_expiry` is later than the current one (or than the new
+/// tag's default when the tag changes too). No expiry counts as later.
+fn pushes_back_limited_expiry(
+    cfg: &Config,
+    entry: &ReportEntry,
+    new_tag: Option<&str>,
+    new_expiry: &Option<String>,
+) -> Result<bool, String> {
+    let tag = new_tag.unwrap_or(&entry.tag);
+    let limited = cfg
+        .tag(tag)
+        .is_some_and(|(_, tc)| tc.max_extensions.is_some() || tc.max_total_days.is_some());
+    if !limited {
+        return Ok(false);
+    }
+    let current = match new_tag {
+        Some(t) if t != entry.tag => {
+            let created = reports::parse_date(&entry.created_at)
+                .unwrap_or_else(|| chrono::Local::now().date_naive());
+            config::expires_at(cfg, t, created)?
+        }
+        _ => entry.expires_at.clone(),
+    };
+    // `None` never expires, so it is later than any date.
+    let date = |d: &Option<String>| d.as_deref().map(reports::parse_date);
+    Ok(match (date(&current), date(new_expiry)) {
+        (Some(Some(now)), Some(Some(new))) => new > now,
+        (Some(_), None) => true,
+        _ => false,
+    })
+}
+
+/// The fields `edit` lets you change in `$EDITOR`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct Editable {
+    message: String,
+    tag: String,
+    range: LineRange,
+    expires_at: Option<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+}
+
+/// YAML shown in the editor for `entry`.
+pub fn to_editor_yaml(entry: &ReportEntry) -> Result<String, String> {
+    let editable = Editable {
+        message: entry.message.clone(),
+        tag: entry.tag.clone(),
+        range: entry.range.clone(),
+        expires_at: entry.expires_at.clone(),
+        assignee: entry.assignee.clone(),
+    };
+    let yaml = serde_yaml::to_string(&editable).map_err(|e| format!("serialize entry: {}", e))?;
+    Ok(format!(
+        "# Editing {} ({}). Save and quit to apply; empty the file to abort.\n{}",
+        entry.id, entry.path, yaml
+    ))
+}
+
+/// Parse the edited YAML back into an `Edit` of the fields that differ from `entry`.
+/// `None` means the file was emptied (abort).
+pub fn from_editor_yaml(text: &str, entry: &ReportEntry) -> Result<Option<Edit>, String> {
+    let has_content = text
+        .lines()
+        .any(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'));
+    if !has_content {
+        return Ok(None);
+    }
+    let edited: Editable =
+        serde_yaml::from_str(text).map_err(|e| format!("invalid edited entry: {}", e))?;
+    let blank_to_none = |v: Option<String>| v.filter(|s| !s.trim().is_empty());
+    let expires_at = blank_to_none(edited.expires_at);
+    let assignee = blank_to_none(edited.assignee);
+    Ok(Some(Edit {
+        message: (edited.message != entry.message).then_some(edited.message),
+        tag: (edited.tag != entry.tag).then_some(edited.tag),
+        range: (edited.range != entry.range).then_some(edited.range),
+        expires_at: (expires_at != entry.expires_at).then_some(expires_at),
+        assignee: (assignee != entry.assignee).then_some(assignee),
+        keep_expiry: false,
+    }))
+}
+
+/// Open `initial` in `$VISUAL` / `$EDITOR` (default `vi`) and return the saved text.
+pub fn run_editor(initial: &str, name: &str) -> Result<String, String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let path =
+        std::env::temp_dir().join(format!("codereport-{}-{}.yaml", name, std::process::id()));
+    std::fs::write(&path, initial).map_err(|e| format!("write {}: {}", path.display(), e))?;
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status();
+    let result = match status {
+        Ok(s) if s.success() => {
+            std::fs::read_to_string(&path).map_err(|e| format!("read {}: {}", path.display(), e))
+        }
+        Ok(s) => Err(format!("editor '{}' exited with {}", editor, s)),
+        Err(e) => Err(format!("run editor '{}': {}", editor, e)),
+    };
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> ReportEntry {
+        ReportEntry::sample("CR-000001", "x.rs")
+    }
+
+    #[test]
+    fn tag_change_recomputes_expiry_and_records_history() {
+        let cfg = config::default_config();
+        let mut e = entry();
+        let edit = Edit {
+            tag: Some("Critical".to_string()),
+            range: Some(LineRange { start: 2, end: 3 }),
+            ..Edit::default()
+        };
+        let changed = edit.apply(&cfg, &mut e, Some("a\nb\nc\n"), None).unwrap();
+        assert_eq!(changed, vec!["tag", "range", "expires_at"]);
+        assert_eq!(e.tag, "critical");
+        assert_eq!(e.expires_at.as_deref(), Some("2026-01-15"));
+        assert!(e.anchor.is_some());
+        assert_eq!(e.history.len(), 3);
+
+        let mut kept = entry();
+        let edit = Edit {
+            tag: Some("refactor".to_string()),
+            keep_expiry: true,
+            ..Edit::default()
+        };
+        edit.apply(&cfg, &mut kept, None, None).unwrap();
+        assert_eq!(kept.expires_at, None);
+
+        let past_eof = Edit {
+            range: Some(LineRange { start: 1, end: 9 }),
+            ..Edit::default()
+        };
+        assert!(past_eof.apply(&cfg, &mut kept, Some("a\n"), None).is_err());
+        assert!(Edit {
+            tag: Some("nope".to_string()),
+            ..Edit::default()
+        }
+        .apply(&cfg, &mut kept, None, None)
+        .is_err());
+    }
+
+    #[test]
+    fn later_expiry_on_limited_tag_must_go_through_extend() {
+        let cfg = config::default_config();
+        let mut e = ReportEntry {
+            tag: "critical".to_string(),
+            expires_at: Some("2026-01-15".to_string()),
+            ..entry()
+        };
+        let set = |d: Option<&str>| Edit {
+            expires_at: Some(d.map(str::to_string)),
+            ..Edit::default()
+        };
+        assert!(set(Some("2026-03-01"))
+            .apply(&cfg, &mut e, None, None)
+            .is_err());
+        assert!(set(None).apply(&cfg, &mut e, None, None).is_err());
+        assert_eq!(e.expires_at.as_deref(), Some("2026-01-15"));
+        set(Some("2026-01-10"))
+            .apply(&cfg, &mut e, None, None)
+            .unwrap();
+        assert_eq!(e.expires_at.as_deref(), Some("2026-01-10"));
+
+        // Retagging compares against the new tag's default expiry.
+        let mut todo = entry();
+        let retag = Edit {
+            tag: Some("critical".to_string()),
+            expires_at: Some(Some("2099-01-01".to_string())),
+            ..Edit::default()
+        };
+        assert!(retag.apply(&cfg, &mut todo, None, None).is_err());
+
+        // Keeping the expiry across the retag may not outlast the new tag's default either.
+        let keep = Edit {
+            tag: Some("critical".to_string()),
+            keep_expiry: true,
+            ..Edit::default()
+        };
+        assert!(keep.apply(&cfg, &mut todo, None, None).is_err());
+        let mut later = ReportEntry {
+            expires_at: Some("2026-02-01".to_string()),
+            ..entry()
+        };
+        assert!(keep.apply(&cfg, &mut later, None, None).is_err());
+        let mut sooner = ReportEntry {
+            expires_at: Some("2026-01-10".to_string()),
+            ..entry()
+        };
+        keep.apply(&cfg, &mut sooner, None, None).unwrap();
+        assert_eq!(sooner.expires_at.as_deref(), Some("2026-01-10"));
+
+        set(Some("2099-01-01"))
+            .apply(&cfg, &mut todo, None, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn editor_yaml_round_trip() {
+        let e = entry();
+        let text = to_editor_yaml(&e).unwrap();
+        let unchanged = from_editor_yaml(&text, &e).unwrap().unwrap();
+        assert!(unchanged.is_empty());
+        let edited = text.replace("message: m", "message: fixed typo");
+        let edit = from_editor_yaml(&edited, &e).unwrap().unwrap();
+        assert_eq!(edit.message.as_deref(), Some("fixed typo"));
+        assert!(edit.tag.is_none());
+        assert!(from_editor_yaml("# nothing\n", &e).unwrap().is_none());
+    }
+}
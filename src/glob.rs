@@ -0,0 +1,191 @@
+/// Glob matching for repo-relative paths, with gitignore-style wildcards:
+/// `*` and `?` never cross `/`, `[a-z]` / `[!a-z]` match one character, a `**`
+/// path segment matches zero or more directories, and `\` escapes the next character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Char(char),
+    /// `?`
+    One,
+    /// `*`
+    Star,
+    /// `**/`: empty, or anything ending in `/`.
+    AnyDirs,
+    /// `**` not followed by `/` (e.g. trailing `/**`): anything, including `/`.
+    AnyPath,
+    Class {
+        negated: bool,
+        items: Vec<(char, char)>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    tokens: Vec<Token>,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Glob {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '\\' if i + 1 < chars.len() => {
+                    tokens.push(Token::Char(chars[i + 1]));
+                    i += 2;
+                }
+                '*' if chars.get(i + 1) == Some(&'*') => {
+                    let segment_start = i == 0 || chars[i - 1] == '/';
+                    let mut j = i;
+                    while chars.get(j) == Some(&'*') {
+                        j += 1;
+                    }
+                    match (segment_start, chars.get(j)) {
+                        (true, Some('/')) => {
+                            tokens.push(Token::AnyDirs);
+                            j += 1;
+                        }
+                        (true, None) => tokens.push(Token::AnyPath),
+                        // `a**b` is just `a*b`, as in gitignore.
+                        _ => tokens.push(Token::Star),
+                    }
+                    i = j;
+                }
+                '*' => {
+                    tokens.push(Token::Star);
+                    i += 1;
+                }
+                '?' => {
+                    tokens.push(Token::One);
+                    i += 1;
+                }
+                '[' => match parse_class(&chars[i + 1..]) {
+                    Some((token, used)) => {
+                        tokens.push(token);
+                        i += used + 1;
+                    }
+                    None => {
+                        tokens.push(Token::Char('['));
+                        i += 1;
+                    }
+                },
+                c => {
+                    tokens.push(Token::Char(c));
+                    i += 1;
+                }
+            }
+        }
+        Glob { tokens }
+    }
+
+    /// True if the whole of `path` matches.
+    pub fn matches(&self, path: &str) -> bool {
+        let text: Vec<char> = path.chars().collect();
+        match_from(&self.tokens, &text)
+    }
+
+    /// True if the pattern has no wildcards (it only matches itself).
+    pub fn is_literal(&self) -> bool {
+        self.tokens.iter().all(|t| matches!(t, Token::Char(_)))
+    }
+}
+
+/// Convenience for one-off matches.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    Glob::new(pattern).matches(path)
+}
+
+/// Parse the body of `[...]` (after the `[`). Returns the token and the characters used,
+/// including the closing `]`, or `None` if the class is not closed.
+fn parse_class(chars: &[char]) -> Option<(Token, usize)> {
+    let mut i = 0;
+    let negated = matches!(chars.first(), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+    let mut items = Vec::new();
+    let mut first = true;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == ']' && !first {
+            return Some((Token::Class { negated, items }, i + 1));
+        }
+        first = false;
+        let c = if c == '\\' && i + 1 < chars.len() {
+            i += 1;
+            chars[i]
+        } else {
+            c
+        };
+        if chars.get(i + 1) == Some(&'-') && chars.get(i + 2).is_some_and(|&e| e != ']') {
+            items.push((c, chars[i + 2]));
+            i += 3;
+        } else {
+            items.push((c, c));
+            i += 1;
+        }
+    }
+    None
+}
+
+fn match_from(tokens: &[Token], text: &[char]) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return text.is_empty();
+    };
+    match token {
+        Token::Char(c) => text.first() == Some(c) && match_from(rest, &text[1..]),
+        Token::One => text.first().is_some_and(|&c| c != '/') && match_from(rest, &text[1..]),
+        Token::Class { negated, items } => {
+            text.first().is_some_and(|&c| {
+                c != '/' && items.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }) && match_from(rest, &text[1..])
+        }
+        Token::Star => {
+            for i in 0..=text.len() {
+                if match_from(rest, &text[i..]) {
+                    return true;
+                }
+                if i < text.len() && text[i] == '/' {
+                    break;
+                }
+            }
+            false
+        }
+        Token::AnyDirs => {
+            if match_from(rest, text) {
+                return true;
+            }
+            (0..text.len())
+                .filter(|&i| text[i] == '/')
+                .any(|i| match_from(rest, &text[i + 1..]))
+        }
+        Token::AnyPath => (0..=text.len()).any(|i| match_from(rest, &text[i..])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_and_double_star() {
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(!glob_match("*.rs", "src/main.rs"));
+        assert!(glob_match("src/*/mod.rs", "src/net/mod.rs"));
+        assert!(!glob_match("src/*/mod.rs", "src/net/tcp/mod.rs"));
+        assert!(glob_match("docs/**/*.md", "docs/a.md"));
+        assert!(glob_match("docs/**/*.md", "docs/x/y/a.md"));
+        assert!(glob_match("**/test_*.py", "test_a.py"));
+        assert!(glob_match("**/test_*.py", "pkg/tests/test_a.py"));
+        assert!(glob_match("legacy/**", "legacy/a/b.c"));
+        assert!(!glob_match("legacy/**", "legacy"));
+        assert!(glob_match("file?.[ch]", "file1.c"));
+        assert!(!glob_match("file?.[!ch]", "file1.c"));
+        assert!(glob_match("[a-c]x", "bx"));
+        assert!(glob_match("a**b", "aXb"));
+        assert!(!glob_match("a**b", "a/b"));
+        assert!(glob_match("\\*.rs", "*.rs"));
+        assert!(!glob_match("\\*.rs", "a.rs"));
+        assert!(Glob::new("src/lib.rs").is_literal());
+    }
+}
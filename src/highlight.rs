@@ -0,0 +1,247 @@
+/// Minimal ANSI syntax highlighting for `codereport show`: comments, strings, numbers
+/// and keywords for common languages, picked by file extension.
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+const RUST: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"'],
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+};
+
+const C_LIKE: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    keywords: &[
+        "abstract",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "final",
+        "finally",
+        "for",
+        "func",
+        "function",
+        "go",
+        "if",
+        "implements",
+        "import",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "static",
+        "struct",
+        "switch",
+        "this",
+        "throw",
+        "throws",
+        "true",
+        "try",
+        "type",
+        "typedef",
+        "var",
+        "void",
+        "while",
+    ],
+};
+
+const PYTHON: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "if", "import", "in", "is",
+        "lambda", "None", "not", "or", "pass", "raise", "return", "self", "True", "try", "while",
+        "with", "yield",
+    ],
+};
+
+const SHELL: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "until", "while",
+    ],
+};
+
+const PLAIN: Syntax = Syntax {
+    line_comments: &[],
+    block_comment: None,
+    quotes: &[],
+    keywords: &[],
+};
+
+const COMMENT: &str = "\x1b[2;37m";
+const STRING: &str = "\x1b[32m";
+const NUMBER: &str = "\x1b[36m";
+const KEYWORD: &str = "\x1b[1;35m";
+const RESET: &str = "\x1b[0m";
+
+fn syntax_for(path: &str) -> &'static Syntax {
+    let ext = path.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+    match ext.to_ascii_lowercase().as_str() {
+        "rs" => &RUST,
+        "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "java" | "kt" | "go" | "js" | "jsx" | "ts"
+        | "tsx" | "swift" | "scala" | "dart" => &C_LIKE,
+        "py" => &PYTHON,
+        "sh" | "bash" | "zsh" | "rb" | "yaml" | "yml" | "toml" => &SHELL,
+        _ => &PLAIN,
+    }
+}
+
+/// Highlight `lines` of the file at `path`. Block comments are tracked across lines, so
+/// pass the snippet in order; a comment opened above the snippet is not detected.
+pub fn highlight(path: &str, lines: &[&str]) -> Vec<String> {
+    let syntax = syntax_for(path);
+    if syntax.keywords.is_empty() {
+        return lines.iter().map(|l| l.to_string()).collect();
+    }
+    let mut in_block = false;
+    lines
+        .iter()
+        .map(|line| highlight_line(syntax, line, &mut in_block))
+        .collect()
+}
+
+fn highlight_line(syntax: &Syntax, line: &str, in_block: &mut bool) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        if *in_block {
+            let (_, close) = syntax.block_comment.unwrap_or(("", ""));
+            let end = rest
+                .find(close)
+                .map(|i| i + close.len())
+                .unwrap_or(rest.len());
+            *in_block = end == rest.len() && !rest.ends_with(close);
+            paint(&mut out, COMMENT, &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        if syntax.line_comments.iter().any(|p| rest.starts_with(p)) {
+            paint(&mut out, COMMENT, rest);
+            break;
+        }
+        if let Some((open, _)) = syntax.block_comment {
+            if rest.starts_with(open) {
+                out.push_str(COMMENT);
+                out.push_str(open);
+                out.push_str(RESET);
+                rest = &rest[open.len()..];
+                *in_block = true;
+                continue;
+            }
+        }
+        let c = rest.chars().next().unwrap_or(' ');
+        if syntax.quotes.contains(&c) {
+            let end = string_end(rest, c);
+            paint(&mut out, STRING, &rest[..end]);
+            rest = &rest[end..];
+        } else if c.is_ascii_digit() {
+            let end = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '.'))
+                .unwrap_or(rest.len());
+            paint(&mut out, NUMBER, &rest[..end]);
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if syntax.keywords.contains(&word) {
+                paint(&mut out, KEYWORD, word);
+            } else {
+                out.push_str(word);
+            }
+            rest = &rest[end..];
+        } else {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    out
+}
+
+/// Byte length of the string literal at the start of `s` (through the closing quote,
+/// honouring backslash escapes), or the rest of the line if it is not closed.
+fn string_end(s: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, ch) in s.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return i + ch.len_utf8();
+        }
+    }
+    s.len()
+}
+
+fn paint(out: &mut String, color: &str, text: &str) {
+    out.push_str(color);
+    out.push_str(text);
+    out.push_str(RESET);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_tokens_and_tracks_block_comments() {
+        let lines = highlight(
+            "src/a.rs",
+            &[
+                "let s = \"a \\\" // b\"; // note",
+                "/* start",
+                "end */ fn x() {}",
+            ],
+        );
+        assert_eq!(
+            lines[0],
+            format!(
+                "{KEYWORD}let{RESET} s = {STRING}\"a \\\" // b\"{RESET}; {COMMENT}// note{RESET}"
+            )
+        );
+        assert!(lines[1].starts_with(COMMENT));
+        assert!(lines[2].starts_with(&format!("{COMMENT}end */{RESET}")));
+        assert!(lines[2].contains(&format!("{KEYWORD}fn{RESET}")));
+
+        let plain = highlight("notes.txt", &["let 1"]);
+        assert_eq!(plain[0], "let 1");
+    }
+}
@@ -1,4 +1,6 @@
-use crate::reports::Reports;
+use crate::check;
+use crate::config::{self, Config, Severity};
+use crate::reports::{ReportEntry, Reports};
 use chrono::Utc;
 use std::collections::HashMap;
 use std::path::Path;
@@ -12,10 +14,31 @@ struct DashboardStats {
     expiring_soon: usize,
 }
 
-pub fn generate_html(repo_root: &Path, reports: &Reports) -> Result<std::path::PathBuf, String> {
+/// Open reports per assignee for the workload table.
+struct Workload {
+    assignee: Option<String>,
+    open: usize,
+    blocking: usize,
+    expired: usize,
+}
+
+type Counts = Vec<(String, u32)>;
+type Heatmap = HashMap<String, HashMap<String, u32>>;
+
+/// Fallback palette for tags without a configured `color`.
+const TAG_PALETTE: &[&str] = &[
+    "#3b82f6", "#10b981", "#ec4899", "#14b8a6", "#f97316", "#a855f7", "#84cc16", "#06b6d4",
+];
+
+pub fn generate_html(
+    repo_root: &Path,
+    config: &Config,
+    reports: &Reports,
+) -> Result<std::path::PathBuf, String> {
     let today = Utc::now().format("%Y-%m-%d").to_string();
-    let stats = compute_stats(reports, &today);
+    let stats = compute_stats(config, reports, &today);
     let (tag_counts, file_counts, heatmap) = compute_chart_data(reports);
+    let tag_styles = tag_css(config, tag_counts.iter().map(|(t, _)| t.as_str()));
 
     let max_tag_count = tag_counts.iter().map(|(_, c)| *c).max().unwrap_or(1) as f64;
     let tag_bars: String = tag_counts
@@ -87,6 +110,29 @@ pub fn generate_html(repo_root: &Path, reports: &Reports) -> Result<std::path::P
         })
         .collect();
 
+    let workload = compute_workload(config, reports, Utc::now().date_naive());
+    let max_open = workload.iter().map(|w| w.open).max().unwrap_or(1).max(1) as f64;
+    let workload_rows: String = workload
+        .iter()
+        .map(|w| {
+            let name = w.assignee.as_deref().unwrap_or("Unassigned");
+            let pct = w.open as f64 / max_open * 100.0;
+            format!(
+                "<tr><td class=\"path-cell\" title=\"{}\">{}</td><td><div class=\"bar-wrap\"><div class=\"bar workload-bar\" style=\"width:{}%\"></div></div></td><td>{}</td><td class=\"{}\">{}</td><td class=\"{}\">{}</td></tr>",
+                escape_attr(name),
+                escape_html(name),
+                pct,
+                w.open,
+                if w.blocking > 0 { "heat danger-cell" } else { "" },
+                w.blocking,
+                if w.expired > 0 { "heat danger-cell" } else { "" },
+                w.expired
+            )
+        })
+        .collect();
+
+    let report_items: String = reports.entries.iter().map(report_details).collect();
+
     let html = format!(
         r##"<!DOCTYPE html>
 <html lang="en">
@@ -136,16 +182,8 @@ body {{ font-family: var(--font); margin: 0; background: var(--bg); color: var(-
 .bar-row {{ display: flex; align-items: center; gap: 12px; }}
 .bar-label {{ width: 86px; flex-shrink: 0; font-size: 13px; color: var(--text); }}
 .bar-label.tag-dot::before {{ content: ''; display: inline-block; width: 6px; height: 6px; border-radius: 50%; margin-right: 6px; vertical-align: 0.15em; }}
-.bar-label.tag-dot.critical::before {{ background: var(--danger); }}
-.bar-label.tag-dot.buggy::before {{ background: var(--warn); }}
-.bar-label.tag-dot.refactor::before {{ background: #8b5cf6; }}
-.bar-label.tag-dot.todo::before {{ background: var(--muted); }}
 .bar-wrap {{ width: 160px; flex-shrink: 0; height: 8px; background: var(--border); border-radius: 4px; overflow: hidden; }}
 .bar {{ height: 100%; border-radius: 4px; min-width: 2px; transition: width 0.2s ease; }}
-.bar.critical {{ background: var(--danger); }}
-.bar.buggy {{ background: var(--warn); }}
-.bar.refactor {{ background: #8b5cf6; }}
-.bar.todo {{ background: var(--muted); }}
 .bar-value {{ width: 2.2em; text-align: right; font-variant-numeric: tabular-nums; font-size: 13px; color: var(--muted); }}
 
 .heatmap-wrap {{ background: var(--surface); border: 1px solid var(--border); border-radius: var(--radius); overflow: auto; }}
@@ -157,18 +195,31 @@ body {{ font-family: var(--font); margin: 0; background: var(--bg); color: var(-
 .heatmap tbody tr:hover {{ background: rgba(59, 130, 246, 0.06); }}
 .heatmap tbody td {{ text-align: center; color: var(--muted); font-variant-numeric: tabular-nums; }}
 .heatmap .heat {{ font-weight: 600; color: var(--text-strong); }}
-.heatmap .heat.lo.critical {{ background: rgba(239, 68, 68, 0.2); color: #fca5a5; }}
-.heatmap .heat.mid.critical {{ background: rgba(239, 68, 68, 0.35); color: #fecaca; }}
-.heatmap .heat.hi.critical {{ background: rgba(239, 68, 68, 0.5); color: #fee2e2; }}
-.heatmap .heat.lo.buggy {{ background: rgba(245, 158, 11, 0.2); color: #fcd34d; }}
-.heatmap .heat.mid.buggy {{ background: rgba(245, 158, 11, 0.35); color: #fde68a; }}
-.heatmap .heat.hi.buggy {{ background: rgba(245, 158, 11, 0.5); color: #fef3c7; }}
-.heatmap .heat.lo.refactor {{ background: rgba(139, 92, 246, 0.2); color: #c4b5fd; }}
-.heatmap .heat.mid.refactor {{ background: rgba(139, 92, 246, 0.35); color: #ddd6fe; }}
-.heatmap .heat.hi.refactor {{ background: rgba(139, 92, 246, 0.5); color: #ede9fe; }}
-.heatmap .heat.lo.todo {{ background: rgba(107, 114, 128, 0.25); color: #9ca3af; }}
-.heatmap .heat.mid.todo {{ background: rgba(107, 114, 128, 0.4); color: #d1d5db; }}
-.heatmap .heat.hi.todo {{ background: rgba(107, 114, 128, 0.55); color: #e5e7eb; }}
+.heatmap .danger-cell {{ color: var(--danger); }}
+.workload-bar {{ background: var(--accent); }}
+
+.reports {{ display: flex; flex-direction: column; gap: 6px; }}
+.report {{ background: var(--surface); border: 1px solid var(--border); border-radius: var(--radius); }}
+.report summary {{ display: flex; gap: 12px; align-items: baseline; padding: 8px 12px; cursor: pointer; list-style: none; }}
+.report summary::-webkit-details-marker {{ display: none; }}
+.report .report-id {{ font-variant-numeric: tabular-nums; color: var(--muted); flex-shrink: 0; }}
+.report .report-tag {{ flex-shrink: 0; font-size: 12px; }}
+.report .report-tag::before {{ content: ''; display: inline-block; width: 6px; height: 6px; border-radius: 50%; margin-right: 6px; vertical-align: 0.15em; }}
+.report .report-msg {{ flex: 1; color: var(--text-strong); overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }}
+.report .report-loc {{ color: var(--muted); font-size: 12px; white-space: nowrap; }}
+.report .report-status {{ color: var(--muted); font-size: 12px; }}
+.report.closed .report-msg {{ color: var(--muted); text-decoration: line-through; }}
+.report-body {{ border-top: 1px solid var(--border); padding: 10px 12px; font-size: 13px; }}
+.report-body dl {{ display: grid; grid-template-columns: 90px 1fr; gap: 2px 12px; margin: 0 0 8px 0; }}
+.report-body dt {{ color: var(--muted); }}
+.report-body dd {{ margin: 0; }}
+.report-body h4 {{ font-size: 11px; font-weight: 600; text-transform: uppercase; letter-spacing: 0.04em; color: var(--muted); margin: 10px 0 4px 0; }}
+.report-body ul {{ list-style: none; margin: 0; padding: 0; }}
+.report-body li {{ padding: 4px 0; border-bottom: 1px solid var(--border); }}
+.report-body li:last-child {{ border-bottom: none; }}
+.report-body .meta {{ color: var(--muted); font-size: 12px; }}
+.comment-body {{ white-space: pre-wrap; margin-top: 2px; }}
+{}
 </style>
 <link rel="preconnect" href="https://fonts.googleapis.com">
 <link rel="preconnect" href="https://fonts.gstatic.com" crossorigin>
@@ -208,10 +259,30 @@ body {{ font-family: var(--font); margin: 0; background: var(--bg); color: var(-
 </table>
 </div>
 </div>
+
+<div class="section">
+<div class="section-title">Workload by assignee (open reports)</div>
+<div class="heatmap-wrap">
+<table class="heatmap">
+<thead><tr><th>Assignee</th><th></th><th class="tag-th">Open</th><th class="tag-th">Blocking</th><th class="tag-th">Expired</th></tr></thead>
+<tbody>
+{}
+</tbody>
+</table>
+</div>
+</div>
+
+<div class="section">
+<div class="section-title">Reports</div>
+<div class="reports">
+{}
+</div>
+</div>
 </div>
 </body>
 </html>
 "##,
+        tag_styles,
         escape_html(&today),
         stats.total,
         stats.open,
@@ -221,7 +292,9 @@ body {{ font-family: var(--font); margin: 0; background: var(--bg); color: var(-
         stats.expiring_soon,
         tag_bars,
         tag_headers,
-        heatmap_rows
+        heatmap_rows,
+        workload_rows,
+        report_items
     );
 
     let out_dir = repo_root.join(".codereports").join("html");
@@ -231,18 +304,134 @@ body {{ font-family: var(--font); margin: 0; background: var(--bg); color: var(-
     Ok(index_path)
 }
 
-fn tag_slug(tag: &str) -> &'static str {
-    let t = tag.to_lowercase();
-    match t.as_str() {
-        "critical" => "critical",
-        "buggy" => "buggy",
-        "refactor" => "refactor",
-        "todo" => "todo",
-        _ => "todo",
+/// CSS class for a tag. Tags are free-form in reports.yaml, so anything outside
+/// `[a-z0-9_-]` is replaced to keep the class name valid.
+fn tag_slug(tag: &str) -> String {
+    let slug: String = tag
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("tag-{}", slug)
+}
+
+/// Display colour for a tag: config `color` if set, else a stable pick from the palette.
+fn tag_color(config: &Config, tag: &str) -> String {
+    if let Some(color) = config.tag(tag).and_then(|(_, tc)| tc.color.clone()) {
+        return color;
     }
+    let sum: usize = tag.bytes().map(|b| b as usize).sum();
+    TAG_PALETTE[sum % TAG_PALETTE.len()].to_string()
 }
 
-fn compute_stats(reports: &Reports, today: &str) -> DashboardStats {
+/// Parse `#rgb` / `#rrggbb` into components; unknown formats fall back to grey.
+fn hex_to_rgb(color: &str) -> (u8, u8, u8) {
+    let hex = color.trim_start_matches('#');
+    let expanded: String = if hex.len() == 3 {
+        hex.chars().flat_map(|c| [c, c]).collect()
+    } else {
+        hex.to_string()
+    };
+    let channel = |i: usize| {
+        expanded
+            .get(i..i + 2)
+            .and_then(|h| u8::from_str_radix(h, 16).ok())
+    };
+    match (channel(0), channel(2), channel(4)) {
+        (Some(r), Some(g), Some(b)) if expanded.len() == 6 => (r, g, b),
+        _ => (107, 114, 128),
+    }
+}
+
+/// Per-tag rules for the tag dots, bars and heatmap cells.
+fn tag_css<'a>(config: &Config, tags: impl Iterator<Item = &'a str>) -> String {
+    let mut css = String::new();
+    for tag in tags {
+        let slug = tag_slug(tag);
+        let color = tag_color(config, tag);
+        let (r, g, b) = hex_to_rgb(&color);
+        css.push_str(&format!(
+            ".bar-label.tag-dot.{slug}::before {{ background: {color}; }}\n\
+             .bar.{slug} {{ background: {color}; }}\n\
+             .heatmap .heat.lo.{slug} {{ background: rgba({r}, {g}, {b}, 0.2); color: {color}; }}\n\
+             .heatmap .heat.mid.{slug} {{ background: rgba({r}, {g}, {b}, 0.35); }}\n\
+             .heatmap .heat.hi.{slug} {{ background: rgba({r}, {g}, {b}, 0.5); }}\n\
+             .report .report-tag.{slug}::before {{ background: {color}; }}\n"
+        ));
+    }
+    css
+}
+
+/// Collapsible per-report view: summary line, fields, history and comment thread.
+fn report_details(e: &ReportEntry) -> String {
+    let mut body = String::new();
+    let mut field = |name: &str, value: &str| {
+        body.push_str(&format!("<dt>{}</dt><dd>{}</dd>", name, escape_html(value)));
+    };
+    field("Message", &e.message);
+    field("Author", e.author.git.as_deref().unwrap_or("—"));
+    let owners = e.author.all_owners().join(", ");
+    field("Codeowners", if owners.is_empty() { "—" } else { &owners });
+    field("Assignee", e.assignee.as_deref().unwrap_or("—"));
+    field("Created", &e.created_at);
+    field("Expires", e.expires_at.as_deref().unwrap_or("never"));
+    let mut body = format!("<dl>{}</dl>", body);
+
+    if !e.history.is_empty() {
+        body.push_str("<h4>History</h4><ul>");
+        for h in &e.history {
+            let what = h.event.describe().replace(" -> ", " → ");
+            body.push_str(&format!(
+                "<li>{}<div class=\"meta\">{} · {}</div></li>",
+                escape_html(&match h.note {
+                    Some(ref note) => format!("{} ({})", what, note),
+                    None => what,
+                }),
+                escape_html(&h.at),
+                escape_html(h.actor.as_deref().unwrap_or("—"))
+            ));
+        }
+        body.push_str("</ul>");
+    }
+
+    body.push_str(&format!("<h4>Comments ({})</h4>", e.comments.len()));
+    if !e.comments.is_empty() {
+        body.push_str("<ul>");
+        for c in &e.comments {
+            body.push_str(&format!(
+                "<li><div class=\"meta\">{} · {}</div><div class=\"comment-body\">{}</div></li>",
+                escape_html(c.author.as_deref().unwrap_or("—")),
+                escape_html(&c.at),
+                escape_html(&c.body)
+            ));
+        }
+        body.push_str("</ul>");
+    }
+
+    format!(
+        r#"<details class="report{}" id="{}"><summary><span class="report-id">{}</span><span class="report-tag {}">{}</span><span class="report-msg">{}</span><span class="report-loc" title="{}">{}:{}-{}</span><span class="report-status">{}</span></summary><div class="report-body">{}</div></details>"#,
+        if e.is_active() { "" } else { " closed" },
+        escape_attr(&e.id),
+        escape_html(&e.id),
+        tag_slug(&e.tag),
+        escape_html(&e.tag),
+        escape_html(&e.message),
+        escape_attr(&e.path),
+        escape_html(&e.path),
+        e.range.start,
+        e.range.end,
+        escape_html(&e.status),
+        body
+    ) + "\n"
+}
+
+fn compute_stats(config: &Config, reports: &Reports, today: &str) -> DashboardStats {
     let mut open = 0usize;
     let mut resolved = 0usize;
     let mut critical = 0usize;
@@ -250,12 +439,12 @@ fn compute_stats(reports: &Reports, today: &str) -> DashboardStats {
     let mut expiring_soon = 0usize;
 
     for e in &reports.entries {
-        if e.status.eq_ignore_ascii_case("open") {
+        if e.is_active() {
             open += 1;
         } else {
             resolved += 1;
         }
-        if e.tag.eq_ignore_ascii_case("critical") {
+        if config::severity(config, &e.tag) == Ok(Severity::Blocking) {
             critical += 1;
         }
         if let Some(ref exp) = e.expires_at {
@@ -280,6 +469,38 @@ fn compute_stats(reports: &Reports, today: &str) -> DashboardStats {
     }
 }
 
+/// Open reports per assignee, busiest first; unassigned reports are grouped last.
+fn compute_workload(config: &Config, reports: &Reports, today: chrono::NaiveDate) -> Vec<Workload> {
+    let mut by_assignee: HashMap<Option<String>, Workload> = HashMap::new();
+    for e in reports.entries.iter().filter(|e| e.is_active()) {
+        let ev = check::evaluate(config, e, today);
+        let w = by_assignee
+            .entry(e.assignee.clone())
+            .or_insert_with(|| Workload {
+                assignee: e.assignee.clone(),
+                open: 0,
+                blocking: 0,
+                expired: 0,
+            });
+        w.open += 1;
+        if ev.is_blocking() {
+            w.blocking += 1;
+        }
+        if ev.is_expired {
+            w.expired += 1;
+        }
+    }
+    let mut workload: Vec<Workload> = by_assignee.into_values().collect();
+    workload.sort_by(|a, b| {
+        a.assignee
+            .is_none()
+            .cmp(&b.assignee.is_none())
+            .then_with(|| b.open.cmp(&a.open))
+            .then_with(|| a.assignee.cmp(&b.assignee))
+    });
+    workload
+}
+
 /// Days between two YYYY-MM-DD strings (order-insensitive absolute difference).
 fn days_between(a: &str, b: &str) -> i64 {
     let parse = |s: &str| {
@@ -312,16 +533,10 @@ fn escape_attr(s: &str) -> String {
         .replace('"', "&quot;")
 }
 
-fn compute_chart_data(
-    reports: &Reports,
-) -> (
-    Vec<(String, u32)>,
-    Vec<(String, u32)>,
-    HashMap<String, HashMap<String, u32>>,
-) {
+fn compute_chart_data(reports: &Reports) -> (Counts, Counts, Heatmap) {
     let mut tag_counts: HashMap<String, u32> = HashMap::new();
     let mut file_counts: HashMap<String, u32> = HashMap::new();
-    let mut heatmap: HashMap<String, HashMap<String, u32>> = HashMap::new();
+    let mut heatmap: Heatmap = HashMap::new();
 
     for e in &reports.entries {
         *tag_counts.entry(e.tag.clone()).or_insert(0) += 1;
@@ -334,11 +549,36 @@ fn compute_chart_data(
             .or_insert(1);
     }
 
-    let mut tag_vec: Vec<(String, u32)> = tag_counts.into_iter().collect();
-    tag_vec.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut tag_vec: Counts = tag_counts.into_iter().collect();
+    tag_vec.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
 
-    let mut file_vec: Vec<(String, u32)> = file_counts.into_iter().collect();
-    file_vec.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut file_vec: Counts = file_counts.into_iter().collect();
+    file_vec.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
 
     (tag_vec, file_vec, heatmap)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workload_counts_blocking_and_expired_per_assignee() {
+        let mut entries = check::sample_reports();
+        entries[0].assignee = Some("ana".to_string());
+        entries[1].assignee = Some("ana".to_string());
+        // An earlier date that sorts after today's as text.
+        entries[2].expires_at = Some("2026-1-2".to_string());
+        let reports = Reports {
+            version: 1,
+            entries,
+        };
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 1, 4).unwrap();
+        let workload = compute_workload(&config::default_config(), &reports, today);
+        let rows: Vec<(Option<&str>, usize, usize, usize)> = workload
+            .iter()
+            .map(|w| (w.assignee.as_deref(), w.open, w.blocking, w.expired))
+            .collect();
+        assert_eq!(rows, vec![(Some("ana"), 2, 1, 1), (None, 1, 0, 1)]);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::check::Evaluation;
+use crate::output::xml_escape;
+
+/// Render JUnit XML: one testcase per open report; violations are failures.
+pub fn render(items: &[Evaluation]) -> String {
+    let failures = items.iter().filter(|i| i.is_violation()).count();
+    let timestamp = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S");
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"codereport\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+        items.len(),
+        failures
+    ));
+    out.push_str(&format!(
+        "  <testsuite name=\"codereport\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\" timestamp=\"{}\">\n",
+        items.len(),
+        failures,
+        timestamp
+    ));
+    for item in items {
+        let e = item.entry;
+        let name = format!("{} {}:{}-{}", e.id, e.path, e.range.start, e.range.end);
+        let open_tag = format!(
+            "    <testcase classname=\"codereport.{}\" name=\"{}\" file=\"{}\" line=\"{}\"",
+            xml_escape(&e.tag),
+            xml_escape(&name),
+            xml_escape(&e.path),
+            e.range.start
+        );
+        if item.is_violation() {
+            out.push_str(&open_tag);
+            out.push_str(">\n");
+            out.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                xml_escape(&e.tag),
+                xml_escape(&item.failure_reason()),
+                xml_escape(&e.message)
+            ));
+            out.push_str("    </testcase>\n");
+        } else {
+            out.push_str(&open_tag);
+            out.push_str("/>\n");
+        }
+    }
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+
+    #[test]
+    fn violations_are_escaped_failures() {
+        let entries = check::sample_reports();
+        let xml = render(&check::evaluate_samples(&entries));
+        assert!(xml
+            .contains("<testsuites name=\"codereport\" tests=\"3\" failures=\"2\" errors=\"0\">"));
+        assert!(xml.contains(
+            "<testcase classname=\"codereport.critical\" name=\"CR-000001 src/a&amp;b.rs:1-2\" file=\"src/a&amp;b.rs\" line=\"1\">"
+        ));
+        assert!(xml.contains(
+            "<failure type=\"critical\" message=\"blocking severity\">lock &lt;held&gt; &amp; &quot;released&quot;</failure>"
+        ));
+        assert!(xml.contains(
+            "<failure type=\"buggy\" message=\"expired on 2026-01-01 (3 days ago)\">m</failure>"
+        ));
+        assert!(xml.contains("name=\"CR-000003 src/net.rs:1-2\" file=\"src/net.rs\" line=\"1\"/>"));
+        assert_eq!(xml.matches("<testcase").count(), 3);
+        assert!(xml.ends_with("  </testsuite>\n</testsuites>\n"));
+    }
+}
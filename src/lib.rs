@@ -1,6 +1,29 @@
+pub mod anchor;
+pub mod annotations;
 pub mod author;
+pub mod baseline;
+pub mod bulk;
+pub mod changes;
+pub mod check;
+pub mod checkstyle;
 pub mod cli;
+pub mod codeowners;
 pub mod config;
+pub mod doctor;
+pub mod edit;
+pub mod glob;
+pub mod highlight;
 pub mod html;
+pub mod junit;
+pub mod merge;
+pub mod migrate;
+pub mod output;
+pub mod owners;
+pub mod query;
 pub mod repo;
 pub mod reports;
+pub mod sarif;
+pub mod scan;
+pub mod search;
+pub mod show;
+pub mod sync;
@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde_yaml::Value;
+
+use crate::config::IdScheme;
+use crate::reports::{ReportEntry, Reports};
+
+/// Name of the merge driver in `.gitattributes` and git config.
+const DRIVER_NAME: &str = "codereport";
+const ATTRIBUTES_LINE: &str = ".codereports/reports.yaml merge=codereport";
+
+/// Append-only list fields: when both sides add to them, the additions are combined.
+const LIST_FIELDS: [&str; 2] = ["history", "comments"];
+
+#[derive(Debug)]
+pub struct MergeOutcome {
+    pub reports: Reports,
+    /// (old id, new id) for reports added on both sides under the same ID.
+    pub renumbered: Vec<(String, String)>,
+    /// Changes that could not be combined; our side was kept for these.
+    pub conflicts: Vec<String>,
+}
+
+fn to_value(entry: &ReportEntry) -> Value {
+    serde_yaml::to_value(entry).unwrap_or(Value::Null)
+}
+
+fn same(a: &ReportEntry, b: &ReportEntry) -> bool {
+    to_value(a) == to_value(b)
+}
+
+/// Three-way merge of one report by field. Returns the merged entry and the fields both
+/// sides changed differently (ours wins for those).
+fn merge_entry(
+    base: &ReportEntry,
+    ours: &ReportEntry,
+    theirs: &ReportEntry,
+) -> (ReportEntry, Vec<String>) {
+    let (b, o, t) = (to_value(base), to_value(ours), to_value(theirs));
+    if o == b || o == t {
+        return (theirs.clone(), Vec::new());
+    }
+    if t == b {
+        return (ours.clone(), Vec::new());
+    }
+    let as_map = |v: &Value| v.as_mapping().cloned().unwrap_or_default();
+    let (bm, om, tm) = (as_map(&b), as_map(&o), as_map(&t));
+    let mut merged = om.clone();
+    let mut conflicts = Vec::new();
+    let keys: Vec<Value> = om
+        .keys()
+        .chain(tm.keys())
+        .chain(bm.keys())
+        .cloned()
+        .collect();
+    let mut seen = HashSet::new();
+    for key in keys {
+        let name = key.as_str().unwrap_or_default().to_string();
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        let (bv, ov, tv) = (bm.get(&key), om.get(&key), tm.get(&key));
+        if ov == tv || tv == bv {
+            continue;
+        }
+        if ov == bv {
+            match tv {
+                Some(v) => merged.insert(key.clone(), v.clone()),
+                None => merged.remove(&key),
+            };
+        } else if LIST_FIELDS.contains(&name.as_str()) {
+            merged.insert(key.clone(), union_by_time(ov, tv));
+        } else {
+            conflicts.push(name);
+        }
+    }
+    match serde_yaml::from_value(Value::Mapping(merged)) {
+        Ok(entry) => (entry, conflicts),
+        Err(e) => (ours.clone(), vec![format!("entry ({})", e)]),
+    }
+}
+
+/// Our items plus their items we do not have, ordered by their `at` timestamp.
+fn union_by_time(ours: Option<&Value>, theirs: Option<&Value>) -> Value {
+    let seq = |v: Option<&Value>| v.and_then(|v| v.as_sequence().cloned()).unwrap_or_default();
+    let mut items = seq(ours);
+    for item in seq(theirs) {
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    let at = |v: &Value| {
+        v.get("at")
+            .and_then(|a| a.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    items.sort_by_key(at);
+    Value::Sequence(items)
+}
+
+/// Merge report lists by ID. Reports changed on one side take that side; reports changed
+/// on both are merged field by field (history and comments are combined). Reports added
+/// on both sides with the same ID but different content are all kept: the one created
+/// first keeps the ID and the others get new IDs, so the result does not depend on which
+/// side is "ours".
+pub fn merge(base: &Reports, ours: &Reports, theirs: &Reports, scheme: IdScheme) -> MergeOutcome {
+    let base_map: HashMap<&str, &ReportEntry> =
+        base.entries.iter().map(|e| (e.id.as_str(), e)).collect();
+    let theirs_map: HashMap<&str, &ReportEntry> =
+        theirs.entries.iter().map(|e| (e.id.as_str(), e)).collect();
+    let ours_ids: HashSet<&str> = ours.entries.iter().map(|e| e.id.as_str()).collect();
+
+    let mut entries: Vec<ReportEntry> = Vec::new();
+    let mut conflicts = Vec::new();
+    // (index in `entries` of our report, their report with the same ID)
+    let mut collisions: Vec<(usize, ReportEntry)> = Vec::new();
+
+    for o in &ours.entries {
+        let id = o.id.as_str();
+        match (base_map.get(id), theirs_map.get(id)) {
+            (Some(b), Some(t)) => {
+                let (merged, fields) = merge_entry(b, o, t);
+                if !fields.is_empty() {
+                    conflicts.push(format!("{}: both sides changed {}", id, fields.join(", ")));
+                }
+                entries.push(merged);
+            }
+            (Some(b), None) => {
+                if !same(o, b) {
+                    conflicts.push(format!("{}: deleted on their side but changed on ours", id));
+                    entries.push(o.clone());
+                }
+            }
+            (None, Some(t)) => {
+                entries.push(o.clone());
+                if !same(o, t) {
+                    collisions.push((entries.len() - 1, (*t).clone()));
+                }
+            }
+            (None, None) => entries.push(o.clone()),
+        }
+    }
+    for t in &theirs.entries {
+        let id = t.id.as_str();
+        if ours_ids.contains(id) {
+            continue;
+        }
+        match base_map.get(id) {
+            Some(b) if same(t, b) => {}
+            Some(_) => {
+                conflicts.push(format!("{}: deleted on our side but changed on theirs", id));
+                entries.push(t.clone());
+            }
+            None => entries.push(t.clone()),
+        }
+    }
+
+    let mut merged = Reports {
+        version: ours.version,
+        entries,
+    };
+    let mut renumbered = Vec::new();
+    collisions.sort_by(|a, b| a.1.id.cmp(&b.1.id));
+    for (index, theirs_entry) in collisions {
+        let mut pair = [merged.entries[index].clone(), theirs_entry];
+        pair.sort_by_cached_key(|e| {
+            (
+                e.created_at.clone(),
+                serde_yaml::to_string(e).unwrap_or_default(),
+            )
+        });
+        let [keeper, mut moved] = pair;
+        let new_id = merged.new_id(scheme, &format!("{}\n{}", moved.path, moved.message));
+        let old_id = moved.renumber(
+            &new_id,
+            None,
+            "renumbered on merge (ID was taken on another branch)",
+        );
+        renumbered.push((old_id, new_id));
+        merged.entries[index] = keeper;
+        merged.entries.push(moved);
+    }
+    MergeOutcome {
+        reports: merged,
+        renumbered,
+        conflicts,
+    }
+}
+
+/// Register the merge driver: `.gitattributes` (committed, shared) and the repo's git
+/// config (per clone; run `codereport init` after cloning).
+pub fn install_driver(repo_root: &Path) -> Result<(), String> {
+    let path = repo_root.join(".gitattributes");
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    if !content.lines().any(|l| l.trim() == ATTRIBUTES_LINE) {
+        let mut updated = content.trim_end_matches('\n').to_string();
+        if !updated.is_empty() {
+            updated.push('\n');
+        }
+        updated.push_str(ATTRIBUTES_LINE);
+        updated.push('\n');
+        std::fs::write(&path, updated).map_err(|e| format!("write .gitattributes: {}", e))?;
+    }
+    crate::repo::set_local_config(
+        repo_root,
+        &format!("merge.{}.name", DRIVER_NAME),
+        "codereport reports.yaml merge",
+    )?;
+    crate::repo::set_local_config(
+        repo_root,
+        &format!("merge.{}.driver", DRIVER_NAME),
+        "codereport merge-driver %O %A %B",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, message: &str, created_at: &str) -> ReportEntry {
+        ReportEntry {
+            message: message.to_string(),
+            created_at: created_at.to_string(),
+            ..ReportEntry::sample(id, "a.rs")
+        }
+    }
+
+    fn reports(entries: Vec<ReportEntry>) -> Reports {
+        Reports {
+            version: 1,
+            entries,
+        }
+    }
+
+    #[test]
+    fn merges_by_id_and_renumbers_collisions_symmetrically() {
+        let shared = entry("CR-000001", "shared", "2026-01-01");
+        let base = reports(vec![shared.clone()]);
+
+        let mut ours_shared = shared.clone();
+        ours_shared.message = "reworded".to_string();
+        ours_shared.add_comment(None, "from ours");
+        let mut theirs_shared = shared.clone();
+        theirs_shared.status = "in_progress".to_string();
+        theirs_shared.add_comment(None, "from theirs");
+
+        let ours = reports(vec![
+            ours_shared,
+            entry("CR-000002", "added on ours", "2026-02-02"),
+        ]);
+        let theirs = reports(vec![
+            theirs_shared,
+            entry("CR-000002", "added on theirs", "2026-02-01"),
+        ]);
+
+        let out = merge(&base, &ours, &theirs, IdScheme::Sequential);
+        assert!(out.conflicts.is_empty(), "{:?}", out.conflicts);
+        let first = &out.reports.entries[0];
+        assert_eq!(first.message, "reworded");
+        assert_eq!(first.status, "in_progress");
+        assert_eq!(first.comments.len(), 2);
+        // Theirs was created first, so it keeps CR-000002 whichever side is merged into which.
+        assert_eq!(
+            out.renumbered,
+            vec![("CR-000002".to_string(), "CR-000003".to_string())]
+        );
+        let by_id = |r: &Reports, id: &str| {
+            r.entries
+                .iter()
+                .find(|e| e.id == id)
+                .map(|e| e.message.clone())
+        };
+        assert_eq!(by_id(&out.reports, "CR-000002").unwrap(), "added on theirs");
+        assert_eq!(by_id(&out.reports, "CR-000003").unwrap(), "added on ours");
+        let reverse = merge(&base, &theirs, &ours, IdScheme::Sequential);
+        assert_eq!(
+            by_id(&reverse.reports, "CR-000002").unwrap(),
+            "added on theirs"
+        );
+        assert_eq!(
+            by_id(&reverse.reports, "CR-000003").unwrap(),
+            "added on ours"
+        );
+
+        let mut a = shared.clone();
+        a.message = "a".to_string();
+        let mut b = shared.clone();
+        b.message = "b".to_string();
+        let out = merge(
+            &base,
+            &reports(vec![a]),
+            &reports(vec![b]),
+            IdScheme::Sequential,
+        );
+        assert_eq!(out.conflicts, vec!["CR-000001: both sides changed message"]);
+        assert_eq!(out.reports.entries[0].message, "a");
+
+        let deleted = merge(&base, &reports(vec![]), &base, IdScheme::Sequential);
+        assert!(deleted.reports.entries.is_empty());
+    }
+}
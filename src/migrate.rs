@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use serde_yaml::Value;
+
+/// One upgrade of a file's format, from version `from` to `from + 1`.
+pub struct Step {
+    pub from: u32,
+    pub summary: &'static str,
+    apply: fn(&mut Value) -> Result<(), String>,
+}
+
+/// reports.yaml upgrades, in order. The last one ends at `reports::REPORTS_VERSION`.
+pub const REPORTS_STEPS: &[Step] = &[];
+
+/// config.yaml upgrades, in order. The last one ends at `config::CONFIG_VERSION`.
+pub const CONFIG_STEPS: &[Step] = &[];
+
+/// The `version` field of a parsed file (files from before versioning count as 1).
+pub fn version_of(doc: &Value) -> u32 {
+    doc.get("version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(1)
+}
+
+/// Steps that take a file from `from` to `target`.
+pub fn pending(steps: &'static [Step], from: u32, target: u32) -> Vec<&'static Step> {
+    steps
+        .iter()
+        .filter(|s| s.from >= from && s.from < target)
+        .collect()
+}
+
+/// Upgrade `doc` in place to `target`, one step at a time. Returns the version it had.
+/// A file newer than `target` is an error: it was written by a newer codereport.
+pub fn upgrade(doc: &mut Value, steps: &[Step], target: u32, file: &str) -> Result<u32, String> {
+    let original = version_of(doc);
+    if original > target {
+        return Err(format!(
+            "{} is version {}, newer than this codereport supports ({}); upgrade codereport",
+            file, original, target
+        ));
+    }
+    if original == 0 {
+        return Err(format!("unsupported {} version: 0", file));
+    }
+    let mut version = original;
+    while version < target {
+        let step = steps
+            .iter()
+            .find(|s| s.from == version)
+            .ok_or_else(|| format!("no migration for {} from version {}", file, version))?;
+        (step.apply)(doc)
+            .map_err(|e| format!("migrate {} v{} -> v{}: {}", file, version, version + 1, e))?;
+        version += 1;
+        if let Some(map) = doc.as_mapping_mut() {
+            map.insert(Value::from("version"), Value::from(version));
+        }
+    }
+    Ok(original)
+}
+
+/// Upgrade the file at `path` to `target`, keeping a copy of the old file next to it
+/// (`<name>.v<old>.bak`). `validate` must accept the upgraded content before it is
+/// written. Returns the old version and the backup path, or `None` if already current.
+pub fn migrate_file(
+    path: &Path,
+    steps: &[Step],
+    target: u32,
+    validate: impl Fn(&str) -> Result<(), String>,
+) -> Result<Option<(u32, PathBuf)>, String> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("read {}: {}", path.display(), e))?;
+    let mut doc: Value =
+        serde_yaml::from_str(&content).map_err(|e| format!("invalid {}: {}", name, e))?;
+    if version_of(&doc) == target {
+        return Ok(None);
+    }
+    let original = upgrade(&mut doc, steps, target, &name)?;
+    let yaml = serde_yaml::to_string(&doc).map_err(|e| format!("serialize {}: {}", name, e))?;
+    validate(&yaml)?;
+
+    let backup = path.with_file_name(format!("{}.v{}.bak", name, original));
+    std::fs::copy(path, &backup).map_err(|e| format!("back up {}: {}", name, e))?;
+    let temp = path.with_file_name(format!("{}.tmp", name));
+    std::fs::write(&temp, yaml).map_err(|e| format!("write {}: {}", name, e))?;
+    std::fs::rename(&temp, path).map_err(|e| format!("rename {}: {}", name, e))?;
+    Ok(Some((original, backup)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_state(doc: &mut Value) -> Result<(), String> {
+        let map = doc.as_mapping_mut().ok_or("not a mapping")?;
+        if let Some(v) = map.remove("state") {
+            map.insert(Value::from("status"), v);
+        }
+        Ok(())
+    }
+
+    fn require_entries(doc: &mut Value) -> Result<(), String> {
+        match doc.get("entries") {
+            Some(_) => Ok(()),
+            None => Err("no entries".to_string()),
+        }
+    }
+
+    const STEPS: &[Step] = &[
+        Step {
+            from: 1,
+            summary: "rename state to status",
+            apply: rename_state,
+        },
+        Step {
+            from: 2,
+            summary: "require entries",
+            apply: require_entries,
+        },
+    ];
+
+    #[test]
+    fn upgrades_step_by_step() {
+        let mut doc: Value = serde_yaml::from_str(
+            "version: 1
+state: open
+entries: []
+",
+        )
+        .unwrap();
+        assert_eq!(pending(STEPS, 1, 3).len(), 2);
+        assert_eq!(pending(STEPS, 2, 3).len(), 1);
+        assert_eq!(upgrade(&mut doc, STEPS, 3, "test.yaml"), Ok(1));
+        assert_eq!(version_of(&doc), 3);
+        assert_eq!(doc["status"].as_str(), Some("open"));
+
+        // Already current: nothing to do. Newer than supported: refuse. A failing step
+        // names the versions.
+        assert_eq!(upgrade(&mut doc, STEPS, 3, "test.yaml"), Ok(3));
+        let mut newer: Value = serde_yaml::from_str(
+            "version: 4
+",
+        )
+        .unwrap();
+        assert!(upgrade(&mut newer, STEPS, 3, "test.yaml")
+            .unwrap_err()
+            .contains("newer"));
+        let mut broken: Value = serde_yaml::from_str(
+            "version: 2
+",
+        )
+        .unwrap();
+        assert_eq!(
+            upgrade(&mut broken, STEPS, 3, "test.yaml"),
+            Err("migrate test.yaml v2 -> v3: no entries".to_string())
+        );
+        // The shipped formats are at their first version.
+        assert!(pending(REPORTS_STEPS, 1, crate::reports::REPORTS_VERSION).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::annotations;
+use crate::check::Evaluation;
+use crate::checkstyle;
+use crate::junit;
+use crate::sarif;
+
+/// Output format for `list` and `check`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable columns
+    #[default]
+    Table,
+    /// A single JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// Output format for `check`: the list formats plus CI / code-scanning reporters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum CheckFormat {
+    /// `ID  path  tag  message` lines (stderr unless --output is given)
+    #[default]
+    Table,
+    /// A single JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+    /// SARIF 2.1.0 for code-scanning integrations
+    Sarif,
+    /// JUnit XML; every open report is a testcase, violations fail
+    Junit,
+    /// Checkstyle XML; every open report is an error entry
+    Checkstyle,
+    /// GitHub Actions workflow commands (inline PR annotations)
+    Github,
+    /// GitLab Code Quality JSON report
+    Gitlab,
+}
+
+impl CheckFormat {
+    /// Test-report formats list every open report so passes show up too.
+    pub fn includes_all_open(self) -> bool {
+        matches!(self, CheckFormat::Junit | CheckFormat::Checkstyle)
+    }
+}
+
+const CSV_HEADER: &[&str] = &[
+    "id",
+    "path",
+    "start",
+    "end",
+    "tag",
+    "severity",
+    "status",
+    "message",
+    "created_at",
+    "expires_at",
+    "is_expired",
+    "days_until_expiry",
+    "author_git",
+    "codeowner",
+    "owners",
+    "assignee",
+];
+
+/// Render evaluations in a machine-readable format. Returns `None` for `Table`,
+/// whose columns differ per command.
+pub fn render(format: Format, items: &[Evaluation]) -> Result<Option<String>, String> {
+    match format {
+        Format::Table => Ok(None),
+        Format::Json => serde_json::to_string_pretty(items)
+            .map(|s| Some(s + "\n"))
+            .map_err(|e| format!("serialize json: {}", e)),
+        Format::Ndjson => {
+            let mut out = String::new();
+            for item in items {
+                let line =
+                    serde_json::to_string(item).map_err(|e| format!("serialize json: {}", e))?;
+                out.push_str(&line);
+                out.push('\n');
+            }
+            Ok(Some(out))
+        }
+        Format::Csv => Ok(Some(render_csv(items))),
+    }
+}
+
+/// Render `check` results. Always returns text; table rows match the classic stderr output.
+pub fn render_check(format: CheckFormat, items: &[Evaluation]) -> Result<String, String> {
+    let list_format = match format {
+        CheckFormat::Table => {
+            let mut out = String::new();
+            for item in items {
+                let e = item.entry;
+                out.push_str(&format!("{}  {}  {}  {}\n", e.id, e.path, e.tag, e.message));
+            }
+            return Ok(out);
+        }
+        CheckFormat::Sarif => return sarif::render(items),
+        CheckFormat::Junit => return Ok(junit::render(items)),
+        CheckFormat::Checkstyle => return Ok(checkstyle::render(items)),
+        CheckFormat::Github => return Ok(annotations::github(items)),
+        CheckFormat::Gitlab => return annotations::gitlab(items),
+        CheckFormat::Json => Format::Json,
+        CheckFormat::Ndjson => Format::Ndjson,
+        CheckFormat::Csv => Format::Csv,
+    };
+    render(list_format, items).map(|s| s.unwrap_or_default())
+}
+
+fn render_csv(items: &[Evaluation]) -> String {
+    let mut out = CSV_HEADER.join(",");
+    out.push('\n');
+    for item in items {
+        let e = item.entry;
+        let row = [
+            e.id.clone(),
+            e.path.clone(),
+            e.range.start.to_string(),
+            e.range.end.to_string(),
+            e.tag.clone(),
+            item.severity
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_default(),
+            e.status.clone(),
+            e.message.clone(),
+            e.created_at.clone(),
+            e.expires_at.clone().unwrap_or_default(),
+            item.is_expired.to_string(),
+            item.days_until_expiry
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            e.author.git.clone().unwrap_or_default(),
+            e.author.codeowner.clone().unwrap_or_default(),
+            e.author.all_owners().join(" "),
+            e.assignee.clone().unwrap_or_default(),
+        ];
+        let fields: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Escape text for XML attribute values and element content.
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            // Other control characters are not allowed in XML 1.0.
+            c if (c as u32) < 0x20 && c != '\t' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break (RFC 4180).
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn xml_escape_specials() {
+        assert_eq!(
+            xml_escape("a < b && \"c\"\u{1}"),
+            "a &lt; b &amp;&amp; &quot;c&quot;"
+        );
+    }
+}
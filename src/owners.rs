@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use crate::author;
+use crate::codeowners::{CodeOwners, Match};
+use crate::reports::{self, Author, HistoryEvent, HistoryRecord, Reports};
+
+/// Recomputed ownership for one report.
+#[derive(Debug)]
+pub struct Refresh {
+    pub index: usize,
+    pub before: Author,
+    pub after: Author,
+    /// The CODEOWNERS rules the new owners come from, one per section.
+    pub rules: Vec<Match>,
+}
+
+fn owners_text(author: &Author) -> Option<String> {
+    let owners = author.all_owners();
+    (!owners.is_empty()).then(|| owners.join(", "))
+}
+
+impl Refresh {
+    /// (field, before, after) for each field that changed.
+    pub fn changes(&self) -> Vec<(&'static str, Option<String>, Option<String>)> {
+        let mut changes = Vec::new();
+        let (from, to) = (owners_text(&self.before), owners_text(&self.after));
+        if from != to {
+            changes.push(("owners", from, to));
+        }
+        if self.before.git != self.after.git {
+            changes.push(("author", self.before.git.clone(), self.after.git.clone()));
+        }
+        changes
+    }
+}
+
+/// Ownership of the active reports at `selected` as CODEOWNERS (and, with `blame`, git
+/// blame) has it now. Only reports whose owners or author would change are returned.
+/// Without a CODEOWNERS file every report loses its owners.
+pub fn refresh(
+    repo_root: &Path,
+    codeowners: Option<&CodeOwners>,
+    reports: &Reports,
+    selected: &[usize],
+    blame: bool,
+) -> Vec<Refresh> {
+    let mut refreshed = Vec::new();
+    for &index in selected {
+        let entry = &reports.entries[index];
+        if !entry.is_active() {
+            continue;
+        }
+        let owners = codeowners
+            .map(|c| c.owners_for(&entry.path))
+            .unwrap_or_default();
+        let rules = codeowners
+            .map(|c| c.matches(&entry.path))
+            .unwrap_or_default();
+        let mut git = entry.author.git.clone();
+        if blame {
+            // Keep the old author when blame has nothing (deleted or uncommitted file).
+            if let Some(email) =
+                author::blame_email(repo_root, &entry.path, entry.range.start, entry.range.end)
+            {
+                git = Some(email);
+            }
+        }
+        let r = Refresh {
+            index,
+            before: entry.author.clone(),
+            after: Author {
+                git,
+                codeowner: owners.first().cloned(),
+                owners,
+            },
+            rules,
+        };
+        if !r.changes().is_empty() {
+            refreshed.push(r);
+        }
+    }
+    refreshed
+}
+
+/// Write the new ownership into `reports`, recording each change in the report's history.
+pub fn apply(reports: &mut Reports, refreshed: Vec<Refresh>, actor: Option<String>) {
+    for r in refreshed {
+        let changes = r.changes();
+        let entry = &mut reports.entries[r.index];
+        for (field, from, to) in changes {
+            entry.history.push(HistoryRecord {
+                at: reports::now_rfc3339(),
+                actor: actor.clone(),
+                event: HistoryEvent::Edit {
+                    field: field.to_string(),
+                    from,
+                    to,
+                },
+                note: Some("owners refresh".to_string()),
+            });
+        }
+        entry.author = r.after;
+    }
+}
+
+/// (id, owner) for each owner of an active report that no rule in CODEOWNERS lists any more.
+pub fn stale_owners(codeowners: &CodeOwners, reports: &Reports) -> Vec<(String, String)> {
+    reports
+        .entries
+        .iter()
+        .filter(|e| e.is_active())
+        .flat_map(|e| {
+            e.author
+                .all_owners()
+                .into_iter()
+                .filter(|o| !codeowners.mentions(o))
+                .map(|o| (e.id.clone(), o.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, path: &str, codeowner: &str, status: &str) -> reports::ReportEntry {
+        reports::ReportEntry {
+            author: Author {
+                git: Some("a@example.com".to_string()),
+                codeowner: Some(codeowner.to_string()),
+                owners: Vec::new(),
+            },
+            status: status.to_string(),
+            ..reports::ReportEntry::sample(id, path)
+        }
+    }
+
+    #[test]
+    fn refreshes_open_reports_and_finds_stale_owners() {
+        let codeowners = CodeOwners::parse("CODEOWNERS", "*.rs @rust @alice\n/docs/ @writers\n");
+        let mut reports = Reports {
+            version: 1,
+            entries: vec![
+                entry("CR-000001", "src/a.rs", "@old-team", "open"),
+                entry("CR-000002", "docs/guide.md", "@writers", "open"),
+                entry("CR-000003", "src/b.rs", "@old-team", "resolved"),
+            ],
+        };
+        assert_eq!(
+            stale_owners(&codeowners, &reports),
+            vec![("CR-000001".to_string(), "@old-team".to_string())]
+        );
+
+        let root = Path::new("/nonexistent");
+        let refreshed = refresh(root, Some(&codeowners), &reports, &[0, 1, 2], false);
+        // CR-000002 is unchanged and CR-000003 is closed.
+        assert_eq!(refreshed.len(), 1);
+        assert_eq!(
+            refreshed[0].rules[0].describe(&codeowners.file),
+            "CODEOWNERS:1 *.rs @rust @alice"
+        );
+        assert_eq!(
+            refreshed[0].changes(),
+            vec![(
+                "owners",
+                Some("@old-team".to_string()),
+                Some("@rust, @alice".to_string())
+            )]
+        );
+        apply(&mut reports, refreshed, None);
+        let author = &reports.entries[0].author;
+        assert_eq!(author.codeowner.as_deref(), Some("@rust"));
+        assert_eq!(author.owners, vec!["@rust", "@alice"]);
+        assert_eq!(author.git.as_deref(), Some("a@example.com"));
+        assert_eq!(reports.entries[0].history.len(), 1);
+        assert!(stale_owners(&codeowners, &reports).is_empty());
+    }
+}
@@ -0,0 +1,703 @@
+//! Filter expressions and sort keys shared by `list`, `check` and `html`.
+//!
+//! ```text
+//! tag:critical AND path:src/net/** AND expires<30d
+//! (owner:@backend OR assignee:me) AND NOT status:wontfix
+//! created>2026-01-01 message~"race"
+//! ```
+//!
+//! Terms are `field op value`; juxtaposed terms are ANDed. `-term` is `NOT term`.
+
+use crate::check::Evaluation;
+use crate::config::Severity;
+use crate::glob::Glob;
+use crate::reports;
+use chrono::{Datelike, NaiveDate};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// What a query is evaluated against besides the report.
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub today: NaiveDate,
+    /// git `user.email`, for `assignee:me`.
+    pub me: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Tag,
+    Status,
+    Severity,
+    Path,
+    Message,
+    Comment,
+    Owner,
+    Author,
+    Assignee,
+    Created,
+    Expires,
+    Is,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Field> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "id" => Field::Id,
+            "tag" => Field::Tag,
+            "status" => Field::Status,
+            "severity" => Field::Severity,
+            "path" | "file" => Field::Path,
+            "message" | "msg" => Field::Message,
+            "comment" | "comments" => Field::Comment,
+            "owner" | "codeowner" => Field::Owner,
+            "author" => Field::Author,
+            "assignee" => Field::Assignee,
+            "created" | "created_at" => Field::Created,
+            "expires" | "expires_at" => Field::Expires,
+            "is" => Field::Is,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    /// `:` or `=`
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `~`: case-insensitive substring
+    Contains,
+}
+
+impl Op {
+    fn holds(self, ord: Ordering) -> bool {
+        match self {
+            Op::Eq | Op::Contains => ord == Ordering::Equal,
+            Op::Ne => ord != Ordering::Equal,
+            Op::Lt => ord == Ordering::Less,
+            Op::Le => ord != Ordering::Greater,
+            Op::Gt => ord == Ordering::Greater,
+            Op::Ge => ord != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Text(String),
+    Glob(Glob, String),
+    Severity(Severity),
+    Date(NaiveDate),
+    /// `30d` / `2w`: days from today (until expiry, or since creation).
+    Days(i64),
+    /// `expires:none`
+    Missing,
+}
+
+#[derive(Debug, Clone)]
+struct Term {
+    field: Field,
+    op: Op,
+    value: Value,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Term(Term),
+}
+
+/// A parsed filter expression.
+#[derive(Debug, Clone)]
+pub struct Query {
+    expr: Expr,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Query, String> {
+        let tokens = lex(input)?;
+        if tokens.is_empty() {
+            return Err("query is empty".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        if let Some(tok) = parser.tokens.get(parser.pos) {
+            return Err(format!("unexpected {} in query", tok.describe()));
+        }
+        Ok(Query { expr })
+    }
+
+    pub fn matches(&self, item: &Evaluation, ctx: &Context) -> bool {
+        eval(&self.expr, item, ctx)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    /// `field op value`, value still raw.
+    Term(String, Op, String),
+    /// A bare word or quoted string: searched in the message.
+    Word(String),
+}
+
+impl Tok {
+    fn describe(&self) -> String {
+        match self {
+            Tok::LParen => "'('".to_string(),
+            Tok::RParen => "')'".to_string(),
+            Tok::And => "AND".to_string(),
+            Tok::Or => "OR".to_string(),
+            Tok::Not => "NOT".to_string(),
+            Tok::Term(f, _, v) => format!("'{}…{}'", f, v),
+            Tok::Word(w) => format!("'{}'", w),
+        }
+    }
+}
+
+fn lex(input: &str) -> Result<Vec<Tok>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Tok::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Tok::RParen);
+            i += 1;
+        } else if c == '-'
+            && chars
+                .get(i + 1)
+                .is_some_and(|n| n.is_alphabetic() || *n == '(')
+        {
+            tokens.push(Tok::Not);
+            i += 1;
+        } else if c == '"' {
+            let (s, used) = quoted(&chars[i..])?;
+            tokens.push(Tok::Word(s));
+            i += used;
+        } else {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            let op = match (chars.get(i), chars.get(i + 1)) {
+                _ if name.is_empty() => None,
+                (Some('!'), Some('=')) => Some((Op::Ne, 2)),
+                (Some('<'), Some('=')) => Some((Op::Le, 2)),
+                (Some('>'), Some('=')) => Some((Op::Ge, 2)),
+                (Some(':'), _) | (Some('='), _) => Some((Op::Eq, 1)),
+                (Some('<'), _) => Some((Op::Lt, 1)),
+                (Some('>'), _) => Some((Op::Gt, 1)),
+                (Some('~'), _) => Some((Op::Contains, 1)),
+                _ => None,
+            };
+            match op {
+                Some((op, len)) => {
+                    i += len;
+                    let value = if chars.get(i) == Some(&'"') {
+                        let (s, used) = quoted(&chars[i..])?;
+                        i += used;
+                        s
+                    } else {
+                        let vstart = i;
+                        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != ')' {
+                            i += 1;
+                        }
+                        chars[vstart..i].iter().collect()
+                    };
+                    if value.is_empty() {
+                        return Err(format!("missing value after '{}'", name));
+                    }
+                    tokens.push(Tok::Term(name, op, value));
+                }
+                None => {
+                    while i < chars.len() && !chars[i].is_whitespace() && chars[i] != ')' {
+                        i += 1;
+                    }
+                    let word: String = chars[start..i].iter().collect();
+                    tokens.push(match word.as_str() {
+                        "AND" | "&&" => Tok::And,
+                        "OR" | "||" => Tok::Or,
+                        "NOT" => Tok::Not,
+                        _ => Tok::Word(word),
+                    });
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse a `"..."` string at the start of `chars`; returns it and the characters used.
+fn quoted(chars: &[char]) -> Result<(String, usize), String> {
+    let mut out = String::new();
+    let mut i = 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                out.push(chars[i + 1]);
+                i += 2;
+            }
+            '"' => return Ok((out, i + 1)),
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err("unterminated quote in query".to_string())
+}
+
+struct Parser {
+    tokens: Vec<Tok>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos)
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.peek() == Some(&Tok::Or) {
+            self.pos += 1;
+            let right = self.and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(Tok::And) => self.pos += 1,
+                Some(Tok::Or) | Some(Tok::RParen) | None => return Ok(left),
+                Some(_) => {}
+            }
+            let right = self.unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let tok = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "query ends unexpectedly".to_string())?;
+        self.pos += 1;
+        match tok {
+            Tok::Not => Ok(Expr::Not(Box::new(self.unary()?))),
+            Tok::LParen => {
+                let inner = self.or()?;
+                if self.peek() != Some(&Tok::RParen) {
+                    return Err("missing ')' in query".to_string());
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Tok::Term(field, op, value) => term(&field, op, &value).map(Expr::Term),
+            Tok::Word(word) => Ok(Expr::Term(Term {
+                field: Field::Message,
+                op: Op::Contains,
+                value: Value::Text(word.to_lowercase()),
+            })),
+            other => Err(format!("unexpected {} in query", other.describe())),
+        }
+    }
+}
+
+/// Build a term, checking that the operator and value make sense for the field.
+fn term(name: &str, op: Op, raw: &str) -> Result<Term, String> {
+    let field = Field::parse(name).ok_or_else(|| format!("unknown query field '{}'", name))?;
+    let ordered = matches!(op, Op::Lt | Op::Le | Op::Gt | Op::Ge);
+    let value = match field {
+        Field::Severity => {
+            if op == Op::Contains {
+                return Err("severity does not support '~'".to_string());
+            }
+            Value::Severity(Severity::from_str(raw)?)
+        }
+        Field::Created | Field::Expires => {
+            if op == Op::Contains {
+                return Err(format!("{} does not support '~'", name));
+            }
+            if raw.eq_ignore_ascii_case("none") && !ordered {
+                Value::Missing
+            } else if let Some(days) = parse_days(raw) {
+                Value::Days(days)
+            } else {
+                Value::Date(reports::parse_date(raw).ok_or_else(|| {
+                    format!(
+                        "invalid date '{}' for {} (use YYYY-MM-DD or e.g. 30d, 2w)",
+                        raw, name
+                    )
+                })?)
+            }
+        }
+        _ if ordered => {
+            return Err(format!("'{}' cannot be compared with < or >", name));
+        }
+        Field::Path if op != Op::Contains => {
+            Value::Glob(Glob::new(raw.trim_start_matches('/')), raw.to_string())
+        }
+        Field::Is => {
+            const IS: &[&str] = &[
+                "active",
+                "open",
+                "closed",
+                "expired",
+                "blocking",
+                "violation",
+                "orphaned",
+                "assigned",
+            ];
+            let v = raw.to_ascii_lowercase();
+            if !IS.contains(&v.as_str()) {
+                return Err(format!(
+                    "unknown 'is:{}' (expected one of: {})",
+                    raw,
+                    IS.join(", ")
+                ));
+            }
+            if op == Op::Contains {
+                return Err("is does not support '~'".to_string());
+            }
+            Value::Text(v)
+        }
+        _ => Value::Text(raw.to_lowercase()),
+    };
+    Ok(Term { field, op, value })
+}
+
+/// `30d`, `2w` -> days.
+fn parse_days(raw: &str) -> Option<i64> {
+    let raw = raw.trim().to_ascii_lowercase();
+    let (num, mult) = if let Some(n) = raw.strip_suffix('d') {
+        (n, 1)
+    } else if let Some(n) = raw.strip_suffix('w') {
+        (n, 7)
+    } else {
+        return None;
+    };
+    num.parse::<i64>().ok().map(|n| n * mult)
+}
+
+fn eval(expr: &Expr, item: &Evaluation, ctx: &Context) -> bool {
+    match expr {
+        Expr::And(a, b) => eval(a, item, ctx) && eval(b, item, ctx),
+        Expr::Or(a, b) => eval(a, item, ctx) || eval(b, item, ctx),
+        Expr::Not(a) => !eval(a, item, ctx),
+        Expr::Term(t) => eval_term(t, item, ctx),
+    }
+}
+
+/// Compare an optional text field: `:` is case-insensitive equality, `~` substring.
+fn text_matches(op: Op, actual: Option<&str>, wanted: &str) -> bool {
+    let actual = actual.map(|a| a.to_lowercase());
+    let hit = match (op, actual.as_deref()) {
+        (Op::Contains, Some(a)) => a.contains(wanted),
+        (_, Some(a)) => a == wanted,
+        (_, None) => wanted == "none",
+    };
+    if op == Op::Ne {
+        !hit
+    } else {
+        hit
+    }
+}
+
+fn eval_term(t: &Term, item: &Evaluation, ctx: &Context) -> bool {
+    let e = item.entry;
+    match (&t.field, &t.value) {
+        (Field::Path, Value::Glob(glob, raw)) => {
+            let dir = raw.trim_start_matches('/').trim_end_matches('/');
+            let hit = glob.matches(&e.path)
+                || (glob.is_literal() && e.path.starts_with(&format!("{}/", dir)));
+            hit != (t.op == Op::Ne)
+        }
+        (Field::Path, Value::Text(v)) => text_matches(t.op, Some(&e.path), v),
+        (Field::Message, Value::Text(v)) => {
+            let hit = e.message.to_lowercase().contains(v.as_str());
+            hit != (t.op == Op::Ne)
+        }
+        (Field::Comment, Value::Text(v)) => {
+            let hit = e
+                .comments
+                .iter()
+                .any(|c| c.body.to_lowercase().contains(v.as_str()));
+            hit != (t.op == Op::Ne)
+        }
+        (Field::Id, Value::Text(v)) => text_matches(t.op, Some(&e.id), v),
+        (Field::Tag, Value::Text(v)) => text_matches(t.op, Some(&e.tag), v),
+        (Field::Status, Value::Text(v)) => text_matches(
+            t.op,
+            Some(&e.status.replace('-', "_")),
+            &v.replace('-', "_"),
+        ),
+        (Field::Owner, Value::Text(v)) => {
+            // Any owner matches; `owner!=x` means none of them is x.
+            let owners = e.author.all_owners();
+            if owners.is_empty() {
+                return text_matches(t.op, None, v);
+            }
+            let op = if t.op == Op::Ne { Op::Eq } else { t.op };
+            let hit = owners.iter().any(|o| text_matches(op, Some(o), v));
+            hit != (t.op == Op::Ne)
+        }
+        (Field::Author, Value::Text(v)) => text_matches(t.op, e.author.git.as_deref(), v),
+        (Field::Assignee, Value::Text(v)) => {
+            let wanted = if v == "me" {
+                match ctx.me {
+                    Some(ref me) => me.to_lowercase(),
+                    None => return t.op == Op::Ne,
+                }
+            } else {
+                v.clone()
+            };
+            text_matches(t.op, e.assignee.as_deref(), &wanted)
+        }
+        (Field::Severity, Value::Severity(s)) => match item.severity {
+            Some(actual) => t.op.holds(actual.cmp(s)),
+            None => t.op == Op::Ne,
+        },
+        (Field::Created, value) => {
+            let date = reports::parse_date(&e.created_at);
+            compare_date(t.op, date, value, |d| (ctx.today - d).num_days())
+        }
+        (Field::Expires, value) => {
+            let date = e.expires_at.as_deref().and_then(reports::parse_date);
+            compare_date(t.op, date, value, |d| (d - ctx.today).num_days())
+        }
+        (Field::Is, Value::Text(v)) => {
+            let hit = match v.as_str() {
+                "active" | "open" => item.is_open(),
+                "closed" => !item.is_open(),
+                "expired" => item.is_expired,
+                "blocking" => item.is_blocking(),
+                "violation" => item.is_violation(),
+                "orphaned" => e.orphaned,
+                "assigned" => e.assignee.is_some(),
+                _ => false,
+            };
+            hit != (t.op == Op::Ne)
+        }
+        _ => false,
+    }
+}
+
+/// `distance` turns the report's date into the number compared with `Nd` values.
+fn compare_date(
+    op: Op,
+    date: Option<NaiveDate>,
+    value: &Value,
+    distance: impl Fn(NaiveDate) -> i64,
+) -> bool {
+    match (value, date) {
+        (Value::Missing, d) => d.is_none() != (op == Op::Ne),
+        (_, None) => op == Op::Ne,
+        (Value::Date(want), Some(d)) => op.holds(d.cmp(want)),
+        (Value::Days(n), Some(d)) => op.holds(distance(d).cmp(n)),
+        _ => false,
+    }
+}
+
+/// One `--sort` key; `-` prefix sorts descending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    field: Field,
+    descending: bool,
+}
+
+impl SortKey {
+    /// Parse a comma-separated list such as `expires_at,-severity`.
+    pub fn parse_list(spec: &str) -> Result<Vec<SortKey>, String> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                let (descending, name) = match s.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, s.strip_prefix('+').unwrap_or(s)),
+                };
+                match Field::parse(name) {
+                    Some(Field::Comment) | Some(Field::Is) | None => {
+                        Err(format!("cannot sort by '{}'", name))
+                    }
+                    Some(field) => Ok(SortKey { field, descending }),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Stable sort by `keys`, in order. Reports missing a value sort last either way.
+pub fn sort(items: &mut [Evaluation], keys: &[SortKey]) {
+    items.sort_by(|a, b| {
+        keys.iter()
+            .map(|k| compare_by(k, a, b))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+fn compare_by(key: &SortKey, a: &Evaluation, b: &Evaluation) -> Ordering {
+    fn text(f: Field, item: &Evaluation) -> Option<String> {
+        let e = item.entry;
+        match f {
+            Field::Id => Some(e.id.clone()),
+            Field::Tag => Some(e.tag.to_lowercase()),
+            Field::Status => Some(e.status.clone()),
+            Field::Path => Some(e.path.clone()),
+            Field::Message => Some(e.message.to_lowercase()),
+            Field::Owner => e.author.codeowner.as_ref().map(|s| s.to_lowercase()),
+            Field::Author => e.author.git.as_ref().map(|s| s.to_lowercase()),
+            Field::Assignee => e.assignee.as_ref().map(|s| s.to_lowercase()),
+            _ => None,
+        }
+    }
+    let (x, y) = match key.field {
+        Field::Severity => (a.severity.map(|s| s as i64), b.severity.map(|s| s as i64)),
+        Field::Created | Field::Expires => {
+            let date = |item: &Evaluation| {
+                let raw = if key.field == Field::Created {
+                    Some(item.entry.created_at.as_str())
+                } else {
+                    item.entry.expires_at.as_deref()
+                };
+                raw.and_then(reports::parse_date)
+                    .map(|d| d.num_days_from_ce() as i64)
+            };
+            (date(a), date(b))
+        }
+        f => {
+            return order_missing_last(text(f, a), text(f, b), key.descending);
+        }
+    };
+    order_missing_last(x, y, key.descending)
+}
+
+fn order_missing_last<T: Ord>(x: Option<T>, y: Option<T>, descending: bool) -> Ordering {
+    match (x, y) {
+        (Some(x), Some(y)) => {
+            if descending {
+                y.cmp(&x)
+            } else {
+                x.cmp(&y)
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::{Author, ReportEntry};
+
+    fn entry(id: &str, path: &str, tag: &str, expires: Option<&str>) -> ReportEntry {
+        ReportEntry {
+            tag: tag.to_string(),
+            message: "Data race in reconnect".to_string(),
+            author: Author {
+                git: Some("a@example.com".to_string()),
+                codeowner: Some("@backend".to_string()),
+                owners: Vec::new(),
+            },
+            created_at: "2026-02-01".to_string(),
+            expires_at: expires.map(str::to_string),
+            ..ReportEntry::sample(id, path)
+        }
+    }
+
+    fn eval_for<'a>(e: &'a ReportEntry, severity: Severity) -> Evaluation<'a> {
+        Evaluation {
+            entry: e,
+            severity: Some(severity),
+            is_expired: false,
+            days_until_expiry: None,
+            extension_violation: None,
+        }
+    }
+
+    #[test]
+    fn parses_and_matches_terms() {
+        let ctx = Context {
+            today: reports::parse_date("2026-03-01").unwrap(),
+            me: Some("me@example.com".to_string()),
+        };
+        let net = entry(
+            "CR-000001",
+            "src/net/tcp.rs",
+            "critical",
+            Some("2026-03-20"),
+        );
+        let ui = entry("CR-000002", "src/ui/mod.rs", "todo", None);
+        let (net, ui) = (
+            eval_for(&net, Severity::Blocking),
+            eval_for(&ui, Severity::Low),
+        );
+        let q = |s: &str| Query::parse(s).unwrap();
+
+        let scoped = q("tag:critical AND path:src/net/** AND expires<30d");
+        assert!(scoped.matches(&net, &ctx));
+        assert!(!scoped.matches(&ui, &ctx));
+        assert!(q("owner:@backend").matches(&ui, &ctx));
+        assert!(q("created>2026-01-01 message~\"RACE\"").matches(&net, &ctx));
+        assert!(!q("created>2026-02-01").matches(&net, &ctx));
+        assert!(q("path:src/ui").matches(&ui, &ctx));
+        assert!(q("(tag:todo OR severity>=high) -expires:none").matches(&net, &ctx));
+        assert!(!q("tag:todo AND NOT expires:none").matches(&ui, &ctx));
+        assert!(q("assignee:none is:open race").matches(&ui, &ctx));
+        assert!(!q("assignee:me").matches(&ui, &ctx));
+
+        assert!(Query::parse("color:red").is_err());
+        assert!(Query::parse("tag<critical").is_err());
+        assert!(Query::parse("(tag:a").is_err());
+        assert!(Query::parse("expires<soon").is_err());
+    }
+
+    #[test]
+    fn sorts_by_keys_with_missing_last() {
+        let a = entry("CR-000001", "a.rs", "todo", Some("2026-05-01"));
+        let b = entry("CR-000002", "b.rs", "critical", None);
+        let c = entry("CR-000003", "c.rs", "buggy", Some("2026-04-01"));
+        let mut items = vec![
+            eval_for(&a, Severity::Low),
+            eval_for(&b, Severity::Blocking),
+            eval_for(&c, Severity::High),
+        ];
+        sort(
+            &mut items,
+            &SortKey::parse_list("expires_at,-severity").unwrap(),
+        );
+        let ids: Vec<&str> = items.iter().map(|i| i.entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["CR-000003", "CR-000001", "CR-000002"]);
+
+        sort(&mut items, &SortKey::parse_list("-severity").unwrap());
+        assert_eq!(items[0].entry.id, "CR-000002");
+        assert!(SortKey::parse_list("comment").is_err());
+    }
+}
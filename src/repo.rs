@@ -12,6 +12,37 @@ pub fn find_repo_root(cwd: &Path) -> Option<PathBuf> {
     }
 }
 
+/// OID of the commit HEAD points at, or `None` (e.g. no commits yet).
+pub fn head_commit(repo_root: &Path) -> Option<String> {
+    let repo = git2::Repository::open(repo_root).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+/// `user.email` from git config (repo, then global), used as the actor in history.
+pub fn git_user_email(repo_root: &Path) -> Option<String> {
+    let repo = git2::Repository::open(repo_root).ok()?;
+    let email = repo.config().ok()?.get_string("user.email").ok()?;
+    let email = email.trim();
+    if email.is_empty() {
+        None
+    } else {
+        Some(email.to_string())
+    }
+}
+
+/// Set `key` in the repository's own git config (`.git/config`).
+pub fn set_local_config(repo_root: &Path, key: &str, value: &str) -> Result<(), String> {
+    let repo = git2::Repository::open(repo_root).map_err(|e| format!("open repo: {}", e))?;
+    let mut config = repo
+        .config()
+        .and_then(|c| c.open_level(git2::ConfigLevel::Local))
+        .map_err(|e| format!("open git config: {}", e))?;
+    config
+        .set_str(key, value)
+        .map_err(|e| format!("set {}: {}", key, e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
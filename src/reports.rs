@@ -1,6 +1,9 @@
+use crate::config::IdScheme;
+use crate::migrate;
 use std::path::Path;
+use std::str::FromStr;
 
-const REPORTS_VERSION: u32 = 1;
+pub const REPORTS_VERSION: u32 = 1;
 const REPORTS_FILENAME: &str = "reports.yaml";
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -11,12 +14,313 @@ pub struct ReportEntry {
     pub tag: String,
     pub message: String,
     pub author: Author,
+    /// Who is expected to fix it (email or @team); set explicitly, unlike `author`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
     pub created_at: String,
     pub expires_at: Option<String>,
     pub status: String,
+    /// Fingerprint of the covered lines, used to follow the code when lines move.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<Anchor>,
+    /// HEAD commit OID when the report was created; `sync` advances it when it
+    /// rewrites `path`, so `path` always names a file in this commit's tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Set when the anchored code can no longer be found.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub orphaned: bool,
+    /// Lifecycle events, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<HistoryRecord>,
+    /// Discussion thread, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+impl ReportEntry {
+    /// Test fixture: an open `todo` report on lines 1-2 of `path`, created 2026-01-01.
+    /// Override fields with struct update syntax (`..ReportEntry::sample(id, path)`).
+    #[cfg(test)]
+    pub fn sample(id: &str, path: &str) -> ReportEntry {
+        ReportEntry {
+            id: id.to_string(),
+            path: path.to_string(),
+            range: LineRange { start: 1, end: 2 },
+            tag: "todo".to_string(),
+            message: "m".to_string(),
+            author: Author {
+                git: None,
+                codeowner: None,
+                owners: Vec::new(),
+            },
+            assignee: None,
+            created_at: "2026-01-01".to_string(),
+            expires_at: None,
+            status: "open".to_string(),
+            anchor: None,
+            commit: None,
+            orphaned: false,
+            history: vec![],
+            comments: vec![],
+        }
+    }
+
+    /// Open, in progress or reopened: still needs work and is subject to `check`.
+    /// Unknown status strings count as active so they are not silently skipped.
+    pub fn is_active(&self) -> bool {
+        Status::from_str(&self.status)
+            .map(|s| s.is_active())
+            .unwrap_or(true)
+    }
+
+    /// Case-insensitive match on `assignee`.
+    pub fn is_assigned_to(&self, who: &str) -> bool {
+        self.assignee
+            .as_deref()
+            .map(|a| a.eq_ignore_ascii_case(who.trim()))
+            .unwrap_or(false)
+    }
+
+    pub fn add_comment(&mut self, author: Option<String>, body: &str) {
+        self.comments.push(Comment {
+            author,
+            at: now_rfc3339(),
+            body: body.to_string(),
+        });
+    }
+
+    /// Number of `extend`s so far and the days they added in total.
+    pub fn extensions(&self) -> (u32, u32) {
+        self.history
+            .iter()
+            .filter_map(|h| match h.event {
+                HistoryEvent::Extension { days, .. } => Some(days),
+                _ => None,
+            })
+            .fold((0, 0), |(n, total): (u32, u32), days| {
+                (n.saturating_add(1), total.saturating_add(days))
+            })
+    }
+
+    /// Push `expires_at` back by `days` from the later of the current expiry and `today`,
+    /// recording the reason in history. Callers check the tag's extension limits.
+    pub fn extend(
+        &mut self,
+        days: u32,
+        today: chrono::NaiveDate,
+        actor: Option<String>,
+        reason: &str,
+    ) -> Result<String, String> {
+        let current = self
+            .expires_at
+            .as_deref()
+            .ok_or_else(|| "report has no expiry to extend".to_string())?;
+        let current_date =
+            parse_date(current).ok_or_else(|| format!("invalid expires_at '{}'", current))?;
+        let to = current_date
+            .max(today)
+            .checked_add_signed(chrono::Duration::days(days as i64))
+            .ok_or_else(|| format!("extending by {} days is out of range", days))?
+            .format("%Y-%m-%d")
+            .to_string();
+        let from = self.expires_at.replace(to.clone());
+        self.history.push(HistoryRecord {
+            at: now_rfc3339(),
+            actor,
+            event: HistoryEvent::Extension {
+                from,
+                to: to.clone(),
+                days,
+            },
+            note: Some(reason.to_string()),
+        });
+        Ok(to)
+    }
+
+    /// Give the entry `new_id`, rewriting mentions of the old ID in its comments and
+    /// history, and record the change with `note`. Returns the old ID.
+    pub fn renumber(&mut self, new_id: &str, actor: Option<String>, note: &str) -> String {
+        let old_id = std::mem::replace(&mut self.id, new_id.to_string());
+        for c in &mut self.comments {
+            c.body = replace_id(&c.body, &old_id, new_id);
+        }
+        for h in &mut self.history {
+            if let Some(ref mut n) = h.note {
+                *n = replace_id(n, &old_id, new_id);
+            }
+            // Earlier renumberings keep the IDs they record.
+            if let HistoryEvent::Edit { field, from, to } = &mut h.event {
+                if field != "id" {
+                    for v in [from, to].into_iter().flatten() {
+                        *v = replace_id(v, &old_id, new_id);
+                    }
+                }
+            }
+        }
+        self.history.push(HistoryRecord {
+            at: now_rfc3339(),
+            actor,
+            event: HistoryEvent::Edit {
+                field: "id".to_string(),
+                from: Some(old_id.clone()),
+                to: Some(new_id.to_string()),
+            },
+            note: Some(note.to_string()),
+        });
+        old_id
+    }
+
+    /// Move to `to` and append a history record. Callers validate the transition.
+    pub fn transition(&mut self, to: Status, actor: Option<String>, note: Option<String>) {
+        let from = std::mem::replace(&mut self.status, to.as_str().to_string());
+        self.history.push(HistoryRecord {
+            at: now_rfc3339(),
+            actor,
+            event: HistoryEvent::Transition {
+                from,
+                to: to.as_str().to_string(),
+            },
+            note,
+        });
+    }
+}
+
+/// Report lifecycle states. Stored as a plain string in `ReportEntry.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    Open,
+    InProgress,
+    Resolved,
+    Wontfix,
+    Duplicate,
+    Reopened,
+}
+
+impl Status {
+    pub const ALL: [Status; 6] = [
+        Status::Open,
+        Status::InProgress,
+        Status::Resolved,
+        Status::Wontfix,
+        Status::Duplicate,
+        Status::Reopened,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Open => "open",
+            Status::InProgress => "in_progress",
+            Status::Resolved => "resolved",
+            Status::Wontfix => "wontfix",
+            Status::Duplicate => "duplicate",
+            Status::Reopened => "reopened",
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(self, Status::Open | Status::InProgress | Status::Reopened)
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase().replace('-', "_");
+        Status::ALL
+            .into_iter()
+            .find(|st| st.as_str() == lower)
+            .ok_or_else(|| format!("unknown status: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Comment {
+    /// git `user.email` of the commenter, if configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// RFC 3339 timestamp.
+    pub at: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HistoryRecord {
+    /// RFC 3339 timestamp.
+    pub at: String,
+    /// git `user.email` of whoever made the change, if configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(flatten)]
+    pub event: HistoryEvent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum HistoryEvent {
+    Transition {
+        from: String,
+        to: String,
+    },
+    /// Expiry pushed back by `extend`; the reason is the record's note.
+    Extension {
+        from: Option<String>,
+        to: String,
+        days: u32,
+    },
+    /// A field changed by `edit`; values are rendered as text (ranges as `start-end`).
+    Edit {
+        field: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+impl HistoryEvent {
+    /// One-line description, e.g. "open -> resolved" or "tag: todo -> refactor".
+    pub fn describe(&self) -> String {
+        match self {
+            HistoryEvent::Transition { from, to } => format!("{} -> {}", from, to),
+            HistoryEvent::Extension { from, to, days } => format!(
+                "extended by {} day{}: {} -> {}",
+                days,
+                if *days == 1 { "" } else { "s" },
+                from.as_deref().unwrap_or("(none)"),
+                to
+            ),
+            HistoryEvent::Edit { field, from, to } => format!(
+                "{}: {} -> {}",
+                field,
+                from.as_deref().unwrap_or("(none)"),
+                to.as_deref().unwrap_or("(none)")
+            ),
+        }
+    }
+}
+
+pub fn now_rfc3339() -> String {
+    chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+/// Content fingerprint: hash of the covered lines plus per-line hashes of the
+/// surrounding context (nearest line last for `before`, first for `after`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Anchor {
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct LineRange {
     pub start: u32,
     pub end: u32,
@@ -25,7 +329,22 @@ pub struct LineRange {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Author {
     pub git: Option<String>,
+    /// First of `owners` (kept for readers of older files and single-owner displays).
     pub codeowner: Option<String>,
+    /// Every CODEOWNERS owner of the path, required sections first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+}
+
+impl Author {
+    /// All owners; files written before `owners` existed only have `codeowner`.
+    pub fn all_owners(&self) -> Vec<&str> {
+        if self.owners.is_empty() {
+            self.codeowner.iter().map(String::as_str).collect()
+        } else {
+            self.owners.iter().map(String::as_str).collect()
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -49,35 +368,179 @@ impl Reports {
         format!("CR-{:06}", n)
     }
 
+    /// A new ID under `scheme` that no entry uses. `seed` (e.g. path and message of the new
+    /// report) goes into the hash scheme.
+    pub fn new_id(&self, scheme: IdScheme, seed: &str) -> String {
+        let taken = |id: &str| self.entries.iter().any(|e| e.id == id);
+        match scheme {
+            IdScheme::Sequential => self.next_id(),
+            IdScheme::Hash => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_nanos())
+                    .unwrap_or_default();
+                let input = format!(
+                    "{}\n{}\n{}\n{}",
+                    seed,
+                    now,
+                    std::process::id(),
+                    random_u64()
+                );
+                let hex = git2::Oid::hash_object(git2::ObjectType::Blob, input.as_bytes())
+                    .map(|oid| oid.to_string())
+                    .unwrap_or_else(|_| format!("{:016x}{:016x}", random_u64(), random_u64()));
+                // Lengthen the hash in the unlikely case of a clash.
+                (8..=hex.len())
+                    .map(|n| format!("CR-{}", &hex[..n]))
+                    .find(|id| !taken(id))
+                    .unwrap_or_else(|| format!("CR-{}", hex))
+            }
+            IdScheme::Ulid => loop {
+                let id = format!("CR-{}", ulid());
+                if !taken(&id) {
+                    return id;
+                }
+            },
+        }
+    }
+
     pub fn add_entry(&mut self, entry: ReportEntry) {
         self.entries.push(entry);
     }
 
-    pub fn delete_by_id(&mut self, id: &str) -> bool {
-        if let Some(pos) = self.entries.iter().position(|e| e.id == id) {
-            self.entries.remove(pos);
-            true
-        } else {
-            false
+    /// IDs used by more than one entry, with their counts, in file order.
+    pub fn duplicate_ids(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for e in &self.entries {
+            match counts.iter_mut().find(|(id, _)| *id == e.id) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((e.id.clone(), 1)),
+            }
+        }
+        counts.retain(|(_, n)| *n > 1);
+        counts
+    }
+
+    /// Index of the single entry with `id`; an error if there is none or it is ambiguous.
+    fn position(&self, id: &str) -> Result<usize, String> {
+        let mut found = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.id == id)
+            .map(|(i, _)| i);
+        match (found.next(), found.count()) {
+            (None, _) => Err(format!("report not found: {}", id)),
+            (Some(i), 0) => Ok(i),
+            (Some(_), more) => Err(format!(
+                "report ID {} is ambiguous ({} reports share it); run `codereport fix-ids`",
+                id,
+                more + 1
+            )),
         }
     }
 
-    pub fn resolve_by_id(&mut self, id: &str) -> bool {
-        if let Some(e) = self.entries.iter_mut().find(|e| e.id == id) {
-            e.status = "resolved".to_string();
-            true
+    pub fn delete_by_id(&mut self, id: &str) -> Result<ReportEntry, String> {
+        let pos = self.position(id)?;
+        Ok(self.entries.remove(pos))
+    }
+
+    pub fn find(&self, id: &str) -> Result<&ReportEntry, String> {
+        let pos = self.position(id)?;
+        Ok(&self.entries[pos])
+    }
+
+    pub fn find_mut(&mut self, id: &str) -> Result<&mut ReportEntry, String> {
+        let pos = self.position(id)?;
+        Ok(&mut self.entries[pos])
+    }
+
+    /// Give every entry but the first of each duplicated ID a new ID under `scheme`,
+    /// recording the change in its history. Returns (old, new) pairs.
+    pub fn fix_duplicate_ids(
+        &mut self,
+        scheme: IdScheme,
+        actor: Option<String>,
+    ) -> Vec<(String, String)> {
+        let mut changed = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for i in 0..self.entries.len() {
+            if seen.insert(self.entries[i].id.clone()) {
+                continue;
+            }
+            let seed = format!("{}\n{}", self.entries[i].path, self.entries[i].message);
+            let new_id = self.new_id(scheme, &seed);
+            let old_id = self.entries[i].renumber(&new_id, actor.clone(), "duplicate ID");
+            seen.insert(new_id.clone());
+            changed.push((old_id, new_id));
+        }
+        changed
+    }
+}
+
+/// `text` with each mention of the report ID `old` replaced by `new`. A match followed by
+/// more ID characters is a longer ID and is left alone.
+fn replace_id(text: &str, old: &str, new: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(old) {
+        let after = &rest[pos + old.len()..];
+        out.push_str(&rest[..pos]);
+        if after.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            out.push_str(old);
         } else {
-            false
+            out.push_str(new);
         }
+        rest = after;
     }
+    out.push_str(rest);
+    out
+}
+
+/// 64 random bits from the standard library's per-process hasher keys.
+fn random_u64() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish()
+}
+
+/// A ULID: 48-bit millisecond timestamp and 80 random bits in Crockford base32.
+fn ulid() -> String {
+    const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let random = ((random_u64() as u128) << 16 | (random_u64() as u128 & 0xffff)) & ((1 << 80) - 1);
+    let value = (millis & ((1 << 48) - 1)) << 80 | random;
+    (0..26)
+        .rev()
+        .map(|i| ALPHABET[((value >> (i * 5)) & 31) as usize] as char)
+        .collect()
+}
+
+/// Parse a `YYYY-MM-DD` date as stored in `created_at` / `expires_at`.
+pub fn parse_date(s: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
 }
 
 fn parse_report_id(id: &str) -> Option<u32> {
     id.strip_prefix("CR-")?.parse().ok()
 }
 
+/// Path of the reports file in `repo_root`.
+pub fn reports_path(repo_root: &Path) -> std::path::PathBuf {
+    repo_root.join(".codereports").join(REPORTS_FILENAME)
+}
+
 pub fn load_reports(repo_root: &Path) -> Result<Reports, String> {
-    let path = repo_root.join(".codereports").join(REPORTS_FILENAME);
+    let path = reports_path(repo_root);
     if !path.exists() {
         return Ok(Reports {
             version: REPORTS_VERSION,
@@ -85,17 +548,57 @@ pub fn load_reports(repo_root: &Path) -> Result<Reports, String> {
         });
     }
     let content = std::fs::read_to_string(&path).map_err(|e| format!("read reports: {}", e))?;
-    let reports: Reports =
-        serde_yaml::from_str(&content).map_err(|e| format!("invalid reports.yaml: {}", e))?;
-    if reports.version != REPORTS_VERSION {
-        return Err(format!(
-            "unsupported reports version: {} (expected {})",
-            reports.version, REPORTS_VERSION
-        ));
+    let (reports, version) = parse_versioned(&content)?;
+    if version < REPORTS_VERSION {
+        eprintln!(
+            "warning: reports.yaml is version {} (current is {}); run `codereport migrate` to upgrade it",
+            version, REPORTS_VERSION
+        );
+    }
+    let duplicates = reports.duplicate_ids();
+    if !duplicates.is_empty() {
+        let list: Vec<String> = duplicates
+            .iter()
+            .map(|(id, n)| format!("{} ({}x)", id, n))
+            .collect();
+        eprintln!(
+            "warning: duplicate report IDs: {}; run `codereport fix-ids`",
+            list.join(", ")
+        );
     }
     Ok(reports)
 }
 
+/// Parse the content of a reports.yaml, upgrading older versions in memory. Empty content
+/// is an empty store.
+pub fn parse_reports(content: &str) -> Result<Reports, String> {
+    parse_versioned(content).map(|(reports, _)| reports)
+}
+
+/// Like `parse_reports`, also returning the version the content was written in.
+fn parse_versioned(content: &str) -> Result<(Reports, u32), String> {
+    if content.trim().is_empty() {
+        return Ok((
+            Reports {
+                version: REPORTS_VERSION,
+                entries: vec![],
+            },
+            REPORTS_VERSION,
+        ));
+    }
+    let mut doc: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|e| format!("invalid reports.yaml: {}", e))?;
+    let version = migrate::upgrade(
+        &mut doc,
+        migrate::REPORTS_STEPS,
+        REPORTS_VERSION,
+        "reports.yaml",
+    )?;
+    let reports: Reports =
+        serde_yaml::from_value(doc).map_err(|e| format!("invalid reports.yaml: {}", e))?;
+    Ok((reports, version))
+}
+
 /// Atomic write: temp file in .codereports then rename.
 pub fn save_reports(repo_root: &Path, reports: &Reports) -> Result<(), String> {
     let dir = repo_root.join(".codereports");
@@ -119,20 +622,98 @@ mod tests {
             entries: vec![],
         };
         assert_eq!(r.next_id(), "CR-000001");
-        r.entries.push(ReportEntry {
-            id: "CR-000001".to_string(),
-            path: "x".to_string(),
-            range: LineRange { start: 1, end: 2 },
-            tag: "todo".to_string(),
-            message: "m".to_string(),
-            author: Author {
-                git: None,
-                codeowner: None,
-            },
-            created_at: "2026-01-01".to_string(),
-            expires_at: None,
-            status: "open".to_string(),
-        });
+        r.entries.push(ReportEntry::sample("CR-000001", "x"));
         assert_eq!(r.next_id(), "CR-000002");
     }
+
+    #[test]
+    fn transition_records_history() {
+        let mut e = ReportEntry::sample("CR-000001", "x");
+        assert!(e.is_active());
+        e.transition(
+            Status::Wontfix,
+            Some("dev@example.com".to_string()),
+            Some("by design".to_string()),
+        );
+        assert_eq!(e.status, "wontfix");
+        assert!(!e.is_active());
+
+        let round: ReportEntry = serde_yaml::from_str(&serde_yaml::to_string(&e).unwrap()).unwrap();
+        assert_eq!(round.history, e.history);
+        assert_eq!(
+            round.history[0].event,
+            HistoryEvent::Transition {
+                from: "open".to_string(),
+                to: "wontfix".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn extend_counts_from_later_of_expiry_and_today() {
+        let mut e = ReportEntry {
+            tag: "critical".to_string(),
+            expires_at: Some("2026-01-15".to_string()),
+            ..ReportEntry::sample("CR-000001", "x")
+        };
+        let day = |s| parse_date(s).unwrap();
+        assert_eq!(
+            e.extend(7, day("2026-01-10"), None, "waiting on upstream")
+                .unwrap(),
+            "2026-01-22"
+        );
+        assert_eq!(
+            e.extend(3, day("2026-02-01"), None, "still waiting")
+                .unwrap(),
+            "2026-02-04"
+        );
+        assert_eq!(e.extensions(), (2, 10));
+        assert_eq!(e.history[1].note.as_deref(), Some("still waiting"));
+        e.expires_at = Some("262000-01-01".to_string());
+        assert!(e.extend(u32::MAX, day("2026-02-01"), None, "r").is_err());
+        assert_eq!(e.history.len(), 2);
+        e.expires_at = None;
+        assert!(e.extend(1, day("2026-02-01"), None, "r").is_err());
+    }
+
+    #[test]
+    fn id_schemes_and_duplicate_repair() {
+        let mut e = ReportEntry::sample("CR-000001", "x");
+        e.add_comment(None, "CR-000001 blocks CR-0000012");
+        let mut r = Reports {
+            version: 1,
+            entries: vec![e.clone(), e.clone(), e],
+        };
+        assert_eq!(r.duplicate_ids(), vec![("CR-000001".to_string(), 3)]);
+        assert!(r.find_mut("CR-000001").unwrap_err().contains("ambiguous"));
+        assert!(r.delete_by_id("CR-000001").is_err());
+        assert!(r.find("CR-000009").unwrap_err().contains("not found"));
+
+        let hash = r.new_id(IdScheme::Hash, "x\nm");
+        assert_eq!(hash.len(), "CR-".len() + 8);
+        assert!(hash[3..].chars().all(|c| c.is_ascii_hexdigit()));
+        let ulid = r.new_id(IdScheme::Ulid, "");
+        assert_eq!(ulid.len(), "CR-".len() + 26);
+        assert_ne!(ulid, r.new_id(IdScheme::Ulid, ""));
+
+        let changed = r.fix_duplicate_ids(IdScheme::Sequential, None);
+        assert_eq!(
+            changed,
+            vec![
+                ("CR-000001".to_string(), "CR-000002".to_string()),
+                ("CR-000001".to_string(), "CR-000003".to_string()),
+            ]
+        );
+        assert!(r.duplicate_ids().is_empty());
+        assert!(r.find("CR-000001").unwrap().history.is_empty());
+        assert_eq!(r.find("CR-000003").unwrap().history.len(), 1);
+        assert_eq!(
+            r.find("CR-000003").unwrap().comments[0].body,
+            "CR-000003 blocks CR-0000012"
+        );
+        assert_eq!(
+            r.find("CR-000001").unwrap().comments[0].body,
+            "CR-000001 blocks CR-0000012"
+        );
+    }
 }
@@ -0,0 +1,143 @@
+use crate::check::Evaluation;
+use crate::config::Severity;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF level for a tag severity.
+fn severity_level(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Blocking) | Some(Severity::High) => "error",
+        Some(Severity::Medium) => "warning",
+        Some(Severity::Low) | None => "note",
+    }
+}
+
+/// Violations (blocking or expired) are always `error`; other reports follow severity.
+fn level(item: &Evaluation) -> &'static str {
+    if item.is_violation() {
+        "error"
+    } else {
+        severity_level(item.severity)
+    }
+}
+
+fn rule_id(tag: &str) -> String {
+    format!("codereport/{}", tag)
+}
+
+/// Render a SARIF 2.1.0 log with one result per report and one rule per tag.
+pub fn render(items: &[Evaluation]) -> Result<String, String> {
+    let mut rules: BTreeMap<&str, Option<Severity>> = BTreeMap::new();
+    for item in items {
+        rules
+            .entry(item.entry.tag.as_str())
+            .or_insert(item.severity);
+    }
+    let rules: Vec<Value> = rules
+        .iter()
+        .map(|(tag, severity)| {
+            json!({
+                "id": rule_id(tag),
+                "name": tag,
+                "shortDescription": { "text": format!("Open '{}' code report", tag) },
+                "defaultConfiguration": { "level": severity_level(*severity) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = items
+        .iter()
+        .map(|item| {
+            let e = item.entry;
+            json!({
+                "ruleId": rule_id(&e.tag),
+                "level": level(item),
+                "message": { "text": format!("{}: {}", e.id, e.message) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": e.path, "uriBaseId": "%SRCROOT%" },
+                        "region": { "startLine": e.range.start, "endLine": e.range.end },
+                    }
+                }],
+                "partialFingerprints": { "codereportId/v1": e.id },
+                "properties": {
+                    "tag": e.tag,
+                    "severity": item.severity.map(|s| s.as_str()),
+                    "expiresAt": e.expires_at,
+                    "isExpired": item.is_expired,
+                },
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codereport",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&log)
+        .map(|s| s + "\n")
+        .map_err(|e| format!("serialize sarif: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+
+    #[test]
+    fn renders_rules_results_and_levels() {
+        let entries = check::sample_reports();
+        let log: Value =
+            serde_json::from_str(&render(&check::evaluate_samples(&entries)).unwrap()).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            rules,
+            vec!["codereport/buggy", "codereport/critical", "codereport/todo"]
+        );
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        let blocking = &results[0];
+        assert_eq!(blocking["level"], "error");
+        // JSON escaping is left to serde: the text round-trips unchanged.
+        assert_eq!(
+            blocking["message"]["text"],
+            "CR-000001: lock <held> & \"released\""
+        );
+        let location = &blocking["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/a&b.rs");
+        assert_eq!(
+            blocking["partialFingerprints"]["codereportId/v1"],
+            "CR-000001"
+        );
+
+        let expired = &results[1];
+        assert_eq!(expired["level"], "error");
+        assert_eq!(expired["properties"]["isExpired"], true);
+        assert_eq!(
+            expired["locations"][0]["physicalLocation"]["region"]["endLine"],
+            9
+        );
+        assert_eq!(results[2]["level"], "note");
+    }
+}
@@ -0,0 +1,161 @@
+use crate::config::ScanConfig;
+use std::path::Path;
+
+/// A comment marker found in the working tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker {
+    /// Repo-relative path with forward slashes.
+    pub path: String,
+    /// 1-based line number.
+    pub line: u32,
+    /// The marker keyword as written (e.g. "FIXME").
+    pub marker: String,
+    /// Tag the marker maps to in config.
+    pub tag: String,
+    /// Comment text after the marker, or the marker itself when empty.
+    pub message: String,
+}
+
+/// Walk the repo (respecting .gitignore) and collect comment markers.
+/// `.git` and `.codereports` are always skipped; files that are not UTF-8 are ignored.
+pub fn scan_repo(repo_root: &Path, cfg: &ScanConfig) -> Result<Vec<Marker>, String> {
+    let repo = git2::Repository::open(repo_root).ok();
+    let mut markers = Vec::new();
+    let mut stack = vec![repo_root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let read = std::fs::read_dir(&dir).map_err(|e| format!("read {}: {}", dir.display(), e))?;
+        let mut children: Vec<_> = read.filter_map(|e| e.ok()).collect();
+        children.sort_by_key(|e| e.file_name());
+        for child in children {
+            let file_type = match child.file_type() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            if file_type.is_symlink() {
+                continue;
+            }
+            let path = child.path();
+            let rel = match path.strip_prefix(repo_root) {
+                Ok(r) => r.to_string_lossy().replace('\\', "/"),
+                Err(_) => continue,
+            };
+            if rel == ".git" || rel == ".codereports" {
+                continue;
+            }
+            if let Some(ref repo) = repo {
+                if repo.is_path_ignored(Path::new(&rel)).unwrap_or(false) {
+                    continue;
+                }
+            }
+            if file_type.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let prefixes = match comment_prefixes(cfg, &path) {
+                Some(p) => p,
+                None => continue,
+            };
+            let text = match std::fs::read_to_string(&path) {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            for (line, marker, message) in scan_text(&text, prefixes, cfg) {
+                let tag = cfg.markers.get(&marker).cloned().unwrap_or_default();
+                markers.push(Marker {
+                    path: rel.clone(),
+                    line,
+                    marker,
+                    tag,
+                    message,
+                });
+            }
+        }
+    }
+    markers.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    Ok(markers)
+}
+
+/// Comment prefixes for a file: by extension first, then by exact file name (e.g. Makefile).
+fn comment_prefixes<'a>(cfg: &'a ScanConfig, path: &Path) -> Option<&'a Vec<String>> {
+    let by_ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(|e| cfg.comments.get(e));
+    by_ext.or_else(|| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| cfg.comments.get(n))
+    })
+}
+
+/// Find markers in comments. Returns (line, marker, message) per hit; at most one per line.
+pub fn scan_text(text: &str, prefixes: &[String], cfg: &ScanConfig) -> Vec<(u32, String, String)> {
+    let mut hits = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let found = prefixes.iter().find_map(|prefix| {
+            line.match_indices(prefix.as_str())
+                .find_map(|(pos, _)| match_marker(&line[pos + prefix.len()..], cfg))
+        });
+        if let Some((marker, message)) = found {
+            hits.push((idx as u32 + 1, marker, message));
+        }
+    }
+    hits
+}
+
+/// Match `MARKER`, `MARKER:` or `MARKER(who):` at the start of a comment body.
+fn match_marker(comment: &str, cfg: &ScanConfig) -> Option<(String, String)> {
+    let body =
+        comment.trim_start_matches(|c: char| c.is_whitespace() || c == '*' || c == '!' || c == '/');
+    let mut best: Option<&String> = None;
+    for marker in cfg.markers.keys() {
+        if !body.starts_with(marker.as_str()) {
+            continue;
+        }
+        let boundary_ok = body[marker.len()..]
+            .chars()
+            .next()
+            .map(|c| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(true);
+        // Markers may share a prefix (e.g. "HACK" and "HACK!"); prefer the longest.
+        if boundary_ok && best.map(|b| marker.len() > b.len()).unwrap_or(true) {
+            best = Some(marker);
+        }
+    }
+    let marker = best?;
+    let mut rest = body[marker.len()..].trim_start();
+    if rest.starts_with('(') {
+        if let Some(close) = rest.find(')') {
+            rest = rest[close + 1..].trim_start();
+        }
+    }
+    let rest = rest.trim_start_matches([':', '-']).trim();
+    let rest = rest.trim_end_matches("*/").trim_end_matches("-->").trim();
+    let message = if rest.is_empty() {
+        marker.clone()
+    } else {
+        rest.to_string()
+    };
+    Some((marker.clone(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_text_finds_markers() {
+        let cfg = ScanConfig::default();
+        let prefixes = vec!["//".to_string(), "/*".to_string()];
+        let text = "fn main() {\n    // TODO(alice): handle errors\n    let url = \"http://x\"; // FIXME retry\n    /* XXX */\n    // TODOS are not markers\n    let todo = 1; // todo lowercase\n}\n";
+        let hits = scan_text(text, &prefixes, &cfg);
+        assert_eq!(
+            hits,
+            vec![
+                (2, "TODO".to_string(), "handle errors".to_string()),
+                (3, "FIXME".to_string(), "retry".to_string()),
+                (4, "XXX".to_string(), "XXX".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::reports::{self, ReportEntry, Reports};
+
+const INDEX_FILENAME: &str = ".search-index";
+/// Stores with at least this many reports go through the index.
+const INDEX_MIN_REPORTS: usize = 500;
+
+const MESSAGE_WEIGHT: f64 = 3.0;
+const TAG_WEIGHT: f64 = 2.0;
+const PATH_WEIGHT: f64 = 1.5;
+const COMMENT_WEIGHT: f64 = 1.0;
+/// Added when the terms appear in the message as one phrase.
+const PHRASE_BONUS: f64 = 2.0;
+
+const HIGHLIGHT: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+type Span = (usize, usize);
+
+/// One search result. Spans are byte ranges of matched words in the original text.
+#[derive(Debug)]
+pub struct Hit<'a> {
+    pub entry: &'a ReportEntry,
+    pub score: f64,
+    message: Vec<Span>,
+    tag: Vec<Span>,
+    path: Vec<Span>,
+    /// (comment index, spans) for comments with a match.
+    comments: Vec<(usize, Vec<Span>)>,
+}
+
+/// Lowercased, de-duplicated search terms.
+fn terms(query: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for (s, e) in words(query) {
+        let t = query[s..e].to_lowercase();
+        if !out.contains(&t) {
+            out.push(t);
+        }
+    }
+    out
+}
+
+/// Byte spans of the alphanumeric runs in `text`.
+fn words(text: &str) -> Vec<Span> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                out.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        out.push((s, text.len()));
+    }
+    out
+}
+
+/// How well `term` matches `word` (both lowercase): 1.0 exact, 0.8 prefix, 0.6 substring,
+/// 0.5 within a small edit distance, 0.0 no match.
+fn quality(term: &str, word: &str) -> f64 {
+    let len = term.chars().count();
+    if word == term {
+        1.0
+    } else if word.starts_with(term) {
+        0.8
+    } else if len >= 3 && word.contains(term) {
+        0.6
+    } else {
+        let max_edits = match len {
+            0..=3 => return 0.0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        if edit_distance(term, word, max_edits) <= max_edits {
+            0.5
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Optimal string alignment distance (adjacent transpositions count as one edit).
+/// Returns `max + 1` as soon as the distance is known to exceed `max`.
+fn edit_distance(a: &str, b: &str, max: usize) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return max + 1;
+    }
+    let mut prev2: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut cur = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        if cur.iter().all(|&d| d > max) {
+            return max + 1;
+        }
+        prev2 = std::mem::replace(&mut prev, cur);
+    }
+    prev[b.len()]
+}
+
+/// Best match quality of `term` in `text`, and the spans of every matching word.
+fn match_text(term: &str, text: &str) -> (f64, Vec<Span>) {
+    let mut best = 0.0f64;
+    let mut spans = Vec::new();
+    for (s, e) in words(text) {
+        let q = quality(term, &text[s..e].to_lowercase());
+        if q > 0.0 {
+            best = best.max(q);
+            spans.push((s, e));
+        }
+    }
+    (best, spans)
+}
+
+fn merge(into: &mut Vec<Span>, spans: Vec<Span>) {
+    into.extend(spans);
+    into.sort_unstable();
+    into.dedup();
+}
+
+fn score_entry<'a>(entry: &'a ReportEntry, terms: &[String]) -> Option<Hit<'a>> {
+    let mut hit = Hit {
+        entry,
+        score: 0.0,
+        message: Vec::new(),
+        tag: Vec::new(),
+        path: Vec::new(),
+        comments: Vec::new(),
+    };
+    let mut matched = 0;
+    let mut total = 0.0;
+    for term in terms {
+        let mut best = 0.0f64;
+        for (weight, text, spans) in [
+            (MESSAGE_WEIGHT, entry.message.as_str(), &mut hit.message),
+            (TAG_WEIGHT, entry.tag.as_str(), &mut hit.tag),
+            (PATH_WEIGHT, entry.path.as_str(), &mut hit.path),
+        ] {
+            let (q, found) = match_text(term, text);
+            best = best.max(weight * q);
+            merge(spans, found);
+        }
+        for (i, c) in entry.comments.iter().enumerate() {
+            let (q, found) = match_text(term, &c.body);
+            if found.is_empty() {
+                continue;
+            }
+            best = best.max(COMMENT_WEIGHT * q);
+            match hit.comments.iter_mut().find(|(n, _)| *n == i) {
+                Some((_, spans)) => merge(spans, found),
+                None => hit.comments.push((i, found)),
+            }
+        }
+        if best > 0.0 {
+            matched += 1;
+            total += best;
+        }
+    }
+    if matched == 0 {
+        return None;
+    }
+    // Reports matching only some of the terms rank below those matching all of them.
+    hit.score = total * matched as f64 / terms.len() as f64;
+    if terms.len() > 1 {
+        let message: Vec<String> = words(&entry.message)
+            .into_iter()
+            .map(|(s, e)| entry.message[s..e].to_lowercase())
+            .collect();
+        if message.join(" ").contains(&terms.join(" ")) {
+            hit.score += PHRASE_BONUS;
+        }
+    }
+    hit.comments.sort_by_key(|(i, _)| *i);
+    Some(hit)
+}
+
+/// Rank `candidates` (indices into `entries`; all entries if `None`), best first.
+fn rank<'a>(
+    entries: &'a [ReportEntry],
+    terms: &[String],
+    candidates: Option<BTreeSet<usize>>,
+) -> Vec<Hit<'a>> {
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut hits: Vec<Hit> = match candidates {
+        Some(set) => set
+            .into_iter()
+            .filter_map(|i| entries.get(i))
+            .filter_map(|e| score_entry(e, terms))
+            .collect(),
+        None => entries
+            .iter()
+            .filter_map(|e| score_entry(e, terms))
+            .collect(),
+    };
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.entry.id.cmp(&b.entry.id))
+    });
+    hits
+}
+
+/// Search the message, tag, path and comments of `entries` for `query`, best match first.
+/// Terms match whole words, prefixes, substrings or words within a small edit distance.
+pub fn search<'a>(entries: &'a [ReportEntry], query: &str) -> Vec<Hit<'a>> {
+    rank(entries, &terms(query), None)
+}
+
+/// Like `search`, but large stores first narrow the candidates with the word index in
+/// `.codereports/.search-index` (rebuilt whenever the reports change).
+pub fn search_reports<'a>(repo_root: &Path, reports: &'a Reports, query: &str) -> Vec<Hit<'a>> {
+    let terms = terms(query);
+    if reports.entries.len() < INDEX_MIN_REPORTS {
+        return rank(&reports.entries, &terms, None);
+    }
+    let index = load_or_build_index(repo_root, reports);
+    rank(&reports.entries, &terms, Some(index.candidates(&terms)))
+}
+
+/// Word -> indices of the entries containing it, for the reports with `fingerprint`.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct Index {
+    fingerprint: String,
+    words: BTreeMap<String, Vec<usize>>,
+}
+
+impl Index {
+    fn build(reports: &Reports, fingerprint: String) -> Index {
+        let mut words_map: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, e) in reports.entries.iter().enumerate() {
+            let texts = [e.message.as_str(), e.tag.as_str(), e.path.as_str()]
+                .into_iter()
+                .chain(e.comments.iter().map(|c| c.body.as_str()));
+            for text in texts {
+                for (s, end) in words(text) {
+                    let ids = words_map.entry(text[s..end].to_lowercase()).or_default();
+                    if ids.last() != Some(&i) {
+                        ids.push(i);
+                    }
+                }
+            }
+        }
+        Index {
+            fingerprint,
+            words: words_map,
+        }
+    }
+
+    /// Entries containing a word that matches any term, by the same rules `rank` uses, so
+    /// the indexed search finds exactly what a scan of every report would. Each distinct
+    /// word is compared once instead of once per report containing it.
+    fn candidates(&self, terms: &[String]) -> BTreeSet<usize> {
+        let mut out = BTreeSet::new();
+        for term in terms {
+            for (word, ids) in &self.words {
+                if quality(term, word) > 0.0 {
+                    out.extend(ids.iter().copied());
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Size and modification time of reports.yaml, or `None` if they cannot be read (the
+/// index is then rebuilt and not saved).
+fn fingerprint(repo_root: &Path) -> Option<String> {
+    let meta = std::fs::metadata(reports::reports_path(repo_root)).ok()?;
+    let modified = meta
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    Some(format!("{}:{}", meta.len(), modified.as_nanos()))
+}
+
+fn load_or_build_index(repo_root: &Path, reports: &Reports) -> Index {
+    let path = repo_root.join(".codereports").join(INDEX_FILENAME);
+    let Some(fingerprint) = fingerprint(repo_root) else {
+        return Index::build(reports, String::new());
+    };
+    if let Ok(content) = std::fs::read_to_string(&path) {
+        if let Ok(index) = serde_json::from_str::<Index>(&content) {
+            if index.fingerprint == fingerprint {
+                return index;
+            }
+        }
+    }
+    let index = Index::build(reports, fingerprint);
+    if let Ok(json) = serde_json::to_string(&index) {
+        let _ = std::fs::write(&path, json);
+    }
+    index
+}
+
+/// Result line plus the message and matching comment lines, matches highlighted.
+pub fn render(hit: &Hit, color: bool) -> String {
+    let e = hit.entry;
+    let mark = |text: &str, spans: &[Span]| -> String {
+        if !color || spans.is_empty() {
+            return text.to_string();
+        }
+        let mut out = String::new();
+        let mut at = 0;
+        for &(s, end) in spans {
+            out.push_str(&text[at..s]);
+            out.push_str(HIGHLIGHT);
+            out.push_str(&text[s..end]);
+            out.push_str(RESET);
+            at = end;
+        }
+        out.push_str(&text[at..]);
+        out
+    };
+    let mut out = format!(
+        "{}  {}:{}-{}  {}  {}  (score {:.1})\n    {}\n",
+        e.id,
+        mark(&e.path, &hit.path),
+        e.range.start,
+        e.range.end,
+        mark(&e.tag, &hit.tag),
+        e.status,
+        hit.score,
+        mark(&e.message, &hit.message),
+    );
+    for (i, spans) in &hit.comments {
+        let c = &e.comments[*i];
+        // Only the lines of the comment that contain a match.
+        let mut offset = 0;
+        for line in c.body.split_inclusive('\n') {
+            let line_end = offset + line.trim_end_matches('\n').len();
+            let in_line: Vec<Span> = spans
+                .iter()
+                .filter(|(s, _)| *s >= offset && *s < line_end)
+                .map(|(s, end)| (s - offset, end - offset))
+                .collect();
+            if !in_line.is_empty() {
+                out.push_str(&format!(
+                    "    comment ({}): {}\n",
+                    c.author.as_deref().unwrap_or("-"),
+                    mark(&c.body[offset..line_end], &in_line).trim()
+                ));
+            }
+            offset += line.len();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, path: &str, tag: &str, message: &str) -> ReportEntry {
+        ReportEntry {
+            tag: tag.to_string(),
+            message: message.to_string(),
+            ..ReportEntry::sample(id, path)
+        }
+    }
+
+    #[test]
+    fn ranks_fuzzy_matches_and_uses_index_consistently() {
+        let mut commented = entry("CR-000003", "src/db.rs", "todo", "Pool size is hardcoded");
+        commented.add_comment(None, "also the retry loop here\nunrelated");
+        let entries = vec![
+            entry("CR-000001", "src/ui.rs", "todo", "Clean up styles"),
+            entry(
+                "CR-000002",
+                "src/net/retry.rs",
+                "buggy",
+                "Retry loop never backs off",
+            ),
+            commented,
+            entry(
+                "CR-000004",
+                "src/net/tcp.rs",
+                "buggy",
+                "Retrying connect spins",
+            ),
+            entry("CR-000005", "src/cli.rs", "todo", "Drop the autoretry flag"),
+        ];
+
+        let ids = |hits: &[Hit]| hits.iter().map(|h| h.entry.id.clone()).collect::<Vec<_>>();
+        let hits = search(&entries, "retry loop");
+        assert_eq!(
+            ids(&hits),
+            vec!["CR-000002", "CR-000003", "CR-000004", "CR-000005"]
+        );
+        // Typo ("retyr" is a transposition) and prefix still find the message.
+        assert_eq!(ids(&search(&entries, "retyr"))[0], "CR-000002");
+        assert_eq!(ids(&search(&entries, "hardc")), vec!["CR-000003"]);
+        assert!(search(&entries, "zzz").is_empty());
+
+        let reports = Reports {
+            version: 1,
+            entries: entries.clone(),
+        };
+        let index = Index::build(&reports, String::new());
+        let retry_loop = terms("retry loop");
+        assert_eq!(
+            ids(&rank(
+                &entries,
+                &retry_loop,
+                Some(index.candidates(&retry_loop))
+            )),
+            ids(&hits)
+        );
+        assert_eq!(index.candidates(&terms("hardc")), BTreeSet::from([2]));
+        assert_eq!(index.candidates(&terms("retyr")), BTreeSet::from([1, 2]));
+        // "retry" matches words exactly, as a prefix and as a substring ("autoretry"):
+        // the index must not stop at the first kind.
+        let retry = terms("retry");
+        let linear = search(&entries, "retry");
+        assert!(ids(&linear).contains(&"CR-000005".to_string()));
+        assert_eq!(
+            ids(&rank(&entries, &retry, Some(index.candidates(&retry)))),
+            ids(&linear)
+        );
+
+        let text = render(&hits[1], false);
+        assert!(text.contains("comment (-): also the retry loop here\n"));
+        assert!(!text.contains("unrelated"));
+        let colored = render(&hits[0], true);
+        assert!(colored.contains(&format!("{HIGHLIGHT}Retry{RESET} {HIGHLIGHT}loop{RESET}")));
+    }
+}
@@ -0,0 +1,239 @@
+use crate::check::Evaluation;
+use crate::codeowners::CodeOwners;
+use crate::highlight;
+
+/// Lines of source printed above and below the report's range.
+const CONTEXT_LINES: u32 = 3;
+
+/// What `render` shows besides the report itself.
+pub struct Extras<'a> {
+    /// Current content of the report's file, if it could be read.
+    pub source: Option<&'a str>,
+    /// Blame authors of the covered lines with their line counts.
+    pub blame: &'a [(String, usize)],
+    /// The repository's CODEOWNERS, to show the rules that match the report's file.
+    pub codeowners: Option<&'a CodeOwners>,
+    /// Emit ANSI colours (syntax highlighting, range marker).
+    pub color: bool,
+}
+
+/// Human-readable view of one report: fields, policy status, blame, source snippet,
+/// history and comment thread.
+pub fn render(item: &Evaluation, extras: &Extras) -> String {
+    let entry = item.entry;
+    let mut out = String::new();
+    let mut field = |name: &str, value: &str| {
+        out.push_str(&format!("{:<10} {}\n", format!("{}:", name), value));
+    };
+    field("id", &entry.id);
+    field(
+        "location",
+        &format!("{}:{}-{}", entry.path, entry.range.start, entry.range.end),
+    );
+    field("tag", &entry.tag);
+    field(
+        "severity",
+        item.severity.map(|s| s.as_str()).unwrap_or("unknown tag"),
+    );
+    field("status", &entry.status);
+    field("message", &entry.message);
+    field("author", entry.author.git.as_deref().unwrap_or("-"));
+    let owners = entry.author.all_owners().join(", ");
+    field("owners", if owners.is_empty() { "-" } else { &owners });
+    field("assignee", entry.assignee.as_deref().unwrap_or("-"));
+    field("created", &entry.created_at);
+    field("expires", &expiry_status(item));
+    let (extensions, extended_days) = entry.extensions();
+    if extensions > 0 {
+        field(
+            "extended",
+            &format!("{} time(s), {} day(s) in total", extensions, extended_days),
+        );
+    }
+    if let Some(ref why) = item.extension_violation {
+        field("policy", why);
+    }
+    if let Some(ref commit) = entry.commit {
+        field("commit", commit);
+    }
+    if entry.orphaned {
+        field("orphaned", "yes (code not found)");
+    }
+
+    if !extras.blame.is_empty() {
+        let who: Vec<String> = extras
+            .blame
+            .iter()
+            .map(|(email, n)| format!("{} ({} line{})", email, n, if *n == 1 { "" } else { "s" }))
+            .collect();
+        field("blame", &who.join(", "));
+    }
+
+    if let Some(codeowners) = extras.codeowners {
+        let rules = codeowners.matches(&entry.path);
+        if !rules.is_empty() {
+            out.push_str("\nCode owners:\n");
+            for m in &rules {
+                out.push_str(&format!("  {}\n", m.describe(&codeowners.file)));
+            }
+        }
+    }
+
+    out.push_str(&format!(
+        "\nSource ({}:{}-{}):\n",
+        entry.path, entry.range.start, entry.range.end
+    ));
+    match extras.source {
+        Some(text) => out.push_str(&snippet(
+            &entry.path,
+            text,
+            entry.range.start,
+            entry.range.end,
+            extras.color,
+        )),
+        None => out.push_str("  (file not found)\n"),
+    }
+
+    if !entry.history.is_empty() {
+        out.push_str("\nHistory:\n");
+        for h in &entry.history {
+            let what = h.event.describe();
+            out.push_str(&format!(
+                "  {}  {}  {}",
+                h.at,
+                h.actor.as_deref().unwrap_or("-"),
+                what
+            ));
+            if let Some(ref note) = h.note {
+                out.push_str(&format!("  ({})", note));
+            }
+            out.push('\n');
+        }
+    }
+
+    if !entry.comments.is_empty() {
+        out.push_str(&format!("\nComments ({}):\n", entry.comments.len()));
+        for c in &entry.comments {
+            out.push_str(&format!(
+                "  {}  {}\n",
+                c.at,
+                c.author.as_deref().unwrap_or("-")
+            ));
+            for line in c.body.lines() {
+                out.push_str(&format!("    {}\n", line));
+            }
+        }
+    }
+    out
+}
+
+/// e.g. "2026-03-01 (12 days left)", "2026-01-01 (3 days overdue)" or "never".
+fn expiry_status(item: &Evaluation) -> String {
+    let Some(ref date) = item.entry.expires_at else {
+        return "never".to_string();
+    };
+    match item.days_until_expiry {
+        Some(0) => format!("{} (today)", date),
+        Some(d) if d > 0 => format!("{} ({} day{} left)", date, d, if d == 1 { "" } else { "s" }),
+        Some(d) => format!(
+            "{} ({} day{} overdue)",
+            date,
+            -d,
+            if d == -1 { "" } else { "s" }
+        ),
+        None => format!("{} (invalid date)", date),
+    }
+}
+
+/// Numbered lines `start..=end` plus context; covered lines are marked with `>`.
+fn snippet(path: &str, text: &str, start: u32, end: u32, color: bool) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let total = lines.len() as u32;
+    if start == 0 || start > total {
+        return format!("  (range is past end of file; {} lines)\n", total);
+    }
+    let from = start.saturating_sub(CONTEXT_LINES).max(1);
+    let to = end.saturating_add(CONTEXT_LINES).min(total);
+    let window = &lines[(from - 1) as usize..to as usize];
+    let rendered: Vec<String> = if color {
+        highlight::highlight(path, window)
+    } else {
+        window.iter().map(|l| l.to_string()).collect()
+    };
+    let width = to.to_string().len();
+    let mut out = String::new();
+    for (n, line) in (from..=to).zip(rendered) {
+        let covered = n >= start && n <= end;
+        let marker = match (covered, color) {
+            (true, true) => "\x1b[1;33m>\x1b[0m",
+            (true, false) => ">",
+            (false, _) => " ",
+        };
+        out.push_str(&format!(
+            "{} {:>width$} | {}\n",
+            marker,
+            n,
+            line,
+            width = width
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Severity;
+    use crate::reports::{Author, LineRange, ReportEntry};
+
+    #[test]
+    fn renders_fields_and_comments() {
+        let mut e = ReportEntry {
+            range: LineRange { start: 3, end: 9 },
+            tag: "buggy".to_string(),
+            message: "race on reconnect".to_string(),
+            author: Author {
+                git: Some("a@example.com".to_string()),
+                codeowner: Some("@net".to_string()),
+                owners: Vec::new(),
+            },
+            assignee: Some("@oncall".to_string()),
+            ..ReportEntry::sample("CR-000042", "src/net.rs")
+        };
+        e.add_comment(Some("b@example.com".to_string()), "seen again\nin CI");
+        let item = Evaluation {
+            entry: &e,
+            severity: Some(Severity::Medium),
+            is_expired: false,
+            days_until_expiry: None,
+            extension_violation: None,
+        };
+        let source: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let blame = vec![("a@example.com".to_string(), 7)];
+        let codeowners = CodeOwners::parse("CODEOWNERS", "* @all\n\n^[Net][2]\n/src/net.rs @net\n");
+        let text = render(
+            &item,
+            &Extras {
+                source: Some(&source),
+                blame: &blame,
+                codeowners: Some(&codeowners),
+                color: false,
+            },
+        );
+        assert!(text.contains("location:  src/net.rs:3-9"));
+        assert!(text.contains("severity:  medium"));
+        assert!(text.contains("assignee:  @oncall"));
+        assert!(text.contains("expires:   never"));
+        assert!(text.contains("blame:     a@example.com (7 lines)"));
+        assert!(text.contains(
+            "Code owners:\n  CODEOWNERS:1 * @all\n  CODEOWNERS:4 /src/net.rs @net [Net, optional, 2 approvals]\n"
+        ));
+        assert!(text.contains("   1 | line 1\n"));
+        assert!(text.contains(">  3 | line 3\n"));
+        assert!(text.contains("  12 | line 12\n"));
+        assert!(!text.contains("line 13"));
+        assert!(text.contains("Comments (1):"));
+        assert!(text.contains("b@example.com"));
+        assert!(text.contains("    in CI\n"));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::anchor::{self, Relocation};
+use crate::reports::{ReportEntry, Reports};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// What `sync_paths` did to one report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncChange {
+    /// The file was renamed or moved; `path` was rewritten.
+    Renamed {
+        id: String,
+        from: String,
+        to: String,
+    },
+    /// The file no longer exists at HEAD; the report is marked orphaned.
+    Deleted { id: String, path: String },
+    /// The report had no recorded commit; HEAD was recorded for it.
+    Stamped { id: String },
+    /// The recorded commit is not in this repository (e.g. shallow clone, rebase).
+    UnknownCommit { id: String, commit: String },
+}
+
+/// Old path -> `Some(new path)` for renames, `None` for deletions, between `from` and HEAD.
+type PathMoves = HashMap<String, Option<String>>;
+
+fn path_moves(
+    repo: &git2::Repository,
+    from: &str,
+    head_tree: &git2::Tree,
+) -> Result<PathMoves, git2::Error> {
+    let old_tree = repo.find_commit(git2::Oid::from_str(from)?)?.tree()?;
+    let mut diff = repo.diff_tree_to_tree(Some(&old_tree), Some(head_tree), None)?;
+    let mut find = git2::DiffFindOptions::new();
+    find.renames(true);
+    diff.find_similar(Some(&mut find))?;
+
+    let mut moves = PathMoves::new();
+    for delta in diff.deltas() {
+        let old = delta
+            .old_file()
+            .path()
+            .map(|p| p.to_string_lossy().replace('\\', "/"));
+        let new = delta
+            .new_file()
+            .path()
+            .map(|p| p.to_string_lossy().replace('\\', "/"));
+        match (delta.status(), old) {
+            (git2::Delta::Renamed, Some(old)) => {
+                moves.insert(old, new);
+            }
+            (git2::Delta::Deleted, Some(old)) => {
+                moves.insert(old, None);
+            }
+            _ => {}
+        }
+    }
+    Ok(moves)
+}
+
+/// Re-find a renamed report's code in its new file, the way `check` does. The report
+/// stays orphaned only if the code cannot be found there either.
+fn relocate_renamed(repo_root: &Path, e: &mut ReportEntry) {
+    let Ok(text) = std::fs::read_to_string(repo_root.join(&e.path)) else {
+        e.orphaned = true;
+        return;
+    };
+    let Some(anchor) = e.anchor.clone() else {
+        // `check` anchors it at the current range.
+        e.orphaned = false;
+        return;
+    };
+    e.orphaned = match anchor::relocate(&text, &e.range, &anchor) {
+        Relocation::Unchanged => false,
+        Relocation::Moved { range, anchor } => {
+            e.range = range;
+            e.anchor = Some(anchor);
+            false
+        }
+        Relocation::Orphaned => true,
+    };
+}
+
+/// Follow renames and deletions from each active report's recorded commit to HEAD.
+/// Reports without a commit get HEAD recorded when their file exists at HEAD. Orphaned
+/// reports are synced too, since `check` orphans a report as soon as its file is moved;
+/// a rename clears the flag if the code is found in the new file. Closed reports are
+/// left alone.
+pub fn sync_paths(repo_root: &Path, reports: &mut Reports) -> Result<Vec<SyncChange>, String> {
+    let repo = git2::Repository::open(repo_root).map_err(|e| format!("open repository: {}", e))?;
+    let head = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| format!("resolve HEAD: {}", e))?;
+    let head_id = head.id().to_string();
+    let head_tree = head.tree().map_err(|e| format!("read HEAD tree: {}", e))?;
+
+    let mut cache: HashMap<String, Option<PathMoves>> = HashMap::new();
+    let mut changes = Vec::new();
+    for e in reports.entries.iter_mut().filter(|e| e.is_active()) {
+        let commit = match e.commit.clone() {
+            Some(c) => c,
+            None => {
+                if head_tree.get_path(Path::new(&e.path)).is_ok() {
+                    e.commit = Some(head_id.clone());
+                    changes.push(SyncChange::Stamped { id: e.id.clone() });
+                }
+                continue;
+            }
+        };
+        if commit == head_id {
+            continue;
+        }
+        let moves = cache
+            .entry(commit.clone())
+            .or_insert_with(|| path_moves(&repo, &commit, &head_tree).ok());
+        let Some(moves) = moves else {
+            changes.push(SyncChange::UnknownCommit {
+                id: e.id.clone(),
+                commit,
+            });
+            continue;
+        };
+        match moves.get(&e.path) {
+            Some(Some(to)) => {
+                let from = std::mem::replace(&mut e.path, to.clone());
+                e.commit = Some(head_id.clone());
+                relocate_renamed(repo_root, e);
+                changes.push(SyncChange::Renamed {
+                    id: e.id.clone(),
+                    from,
+                    to: to.clone(),
+                });
+            }
+            Some(None) if e.orphaned => {}
+            Some(None) => {
+                e.orphaned = true;
+                e.commit = Some(head_id.clone());
+                changes.push(SyncChange::Deleted {
+                    id: e.id.clone(),
+                    path: e.path.clone(),
+                });
+            }
+            None => {}
+        }
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::LineRange;
+
+    fn commit_all(repo: &git2::Repository, message: &str) -> String {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"], None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("t", "t@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+            .to_string()
+    }
+
+    fn entry(id: &str, path: &str, commit: &str) -> ReportEntry {
+        ReportEntry {
+            commit: Some(commit.to_string()),
+            ..ReportEntry::sample(id, path)
+        }
+    }
+
+    #[test]
+    fn follows_renames_and_flags_deletions() {
+        let dir = std::env::temp_dir().join(format!("codereport-sync-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let repo = git2::Repository::init(&dir).unwrap();
+        let body = "fn main() {\n    println!(\"hello\");\n}\n".repeat(5);
+        std::fs::write(dir.join("src/old.rs"), &body).unwrap();
+        std::fs::write(dir.join("gone.rs"), "fn gone() {}\n").unwrap();
+        let first = commit_all(&repo, "init");
+
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        std::fs::rename(dir.join("src/old.rs"), dir.join("lib/new.rs")).unwrap();
+        std::fs::remove_file(dir.join("gone.rs")).unwrap();
+        let second = commit_all(&repo, "move");
+
+        let mut closed = entry("CR-000003", "gone.rs", &first);
+        closed.status = "resolved".to_string();
+        let mut reports = Reports {
+            version: 1,
+            entries: vec![
+                entry("CR-000001", "src/old.rs", &first),
+                entry("CR-000002", "gone.rs", &first),
+                closed,
+            ],
+        };
+        let changes = sync_paths(&dir, &mut reports).unwrap();
+        assert!(changes.contains(&SyncChange::Renamed {
+            id: "CR-000001".to_string(),
+            from: "src/old.rs".to_string(),
+            to: "lib/new.rs".to_string(),
+        }));
+        assert_eq!(reports.entries[0].path, "lib/new.rs");
+        assert_eq!(reports.entries[0].commit.as_deref(), Some(second.as_str()));
+        assert!(reports.entries[1].orphaned);
+        assert_eq!(reports.entries[1].commit.as_deref(), Some(second.as_str()));
+        assert!(!reports.entries[2].orphaned);
+        // Nothing left to do: the deletion is not reported again.
+        assert!(sync_paths(&dir, &mut reports).unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn recovers_reports_orphaned_by_a_rename() {
+        let dir =
+            std::env::temp_dir().join(format!("codereport-sync-orphaned-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let repo = git2::Repository::init(&dir).unwrap();
+        let body: String = (1..=12).map(|i| format!("let x{} = {};\n", i, i)).collect();
+        std::fs::write(dir.join("old.rs"), &body).unwrap();
+        let first = commit_all(&repo, "init");
+
+        std::fs::remove_file(dir.join("old.rs")).unwrap();
+        std::fs::write(dir.join("new.rs"), format!("// moved\n{}", body)).unwrap();
+        commit_all(&repo, "move");
+
+        // What `check` leaves behind after the move: the report is orphaned at old.rs.
+        let e = ReportEntry {
+            range: LineRange { start: 2, end: 3 },
+            anchor: anchor::compute(&body, 2, 3),
+            orphaned: true,
+            ..entry("CR-000001", "old.rs", &first)
+        };
+        let lost = ReportEntry {
+            anchor: anchor::compute("unrelated\ncode\nhere\n", 2, 2),
+            orphaned: true,
+            ..entry("CR-000002", "old.rs", &first)
+        };
+        let mut reports = Reports {
+            version: 1,
+            entries: vec![e, lost],
+        };
+        let changes = sync_paths(&dir, &mut reports).unwrap();
+        assert_eq!(changes.len(), 2);
+        let e = &reports.entries[0];
+        assert_eq!(e.path, "new.rs");
+        assert!(!e.orphaned);
+        assert_eq!((e.range.start, e.range.end), (3, 4));
+        // Followed, but its code is not in the new file either.
+        assert_eq!(reports.entries[1].path, "new.rs");
+        assert!(reports.entries[1].orphaned);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}